@@ -0,0 +1,328 @@
+//! Fills the open boxes of a crossword from a scored word list.
+//!
+//! The search is a backtracking search over entries. It always continues with the entry
+//! that has the fewest fitting words left and gives up on a branch as soon as any entry
+//! has none, trying higher scoring words first. Letters already in the grid are kept.
+//!
+//! Finding a fill doesn't end the search: it goes on until every branch is tried or the
+//! step or time budget runs out, keeping the highest scoring fills found so far.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordData};
+use crate::entry::Direction;
+use crate::utils::Rng;
+use crate::word_list::{bits, WordList};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct AutofillOptions {
+    /// Breaks ties between words with the same score, the same seed always gives the
+    /// same fills.
+    pub seed: u64,
+    /// Stops after placing this many words, `None` for no limit.
+    pub max_steps: Option<u64>,
+    pub time_limit: Option<Duration>,
+    /// How many of the highest scoring fills to return.
+    pub results: usize,
+    /// Entries, by number and direction, whose letters are kept and that don't have to
+    /// be in the word list, which suits theme entries. Their empty boxes are only filled
+    /// by crossing entries. Entries with a rebus box are always kept this way.
+    pub locked: Vec<(u8, Direction)>,
+}
+
+impl Default for AutofillOptions {
+    fn default() -> AutofillOptions {
+        AutofillOptions {
+            seed: 0,
+            max_steps: Some(100_000),
+            time_limit: None,
+            results: 1,
+            locked: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fill {
+    pub crossword: CrosswordData,
+    /// Sum of the scores of the words placed.
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutofillResult {
+    /// The highest scoring fills found, highest first. Fills with the same score are in
+    /// the order they were found.
+    pub fills: Vec<Fill>,
+    pub steps: u64,
+    /// Whether the search ran out of steps or time before it was done, in which case
+    /// higher scoring fills may have been missed.
+    pub stopped: bool,
+}
+
+impl CrosswordData {
+    pub fn autofill(&self, words: &WordList, options: &AutofillOptions) -> AutofillResult {
+        self.fill(words, options, false)
+    }
+
+    /// With `stop_when_full` the search ends as soon as it has `results` fills instead of
+    /// looking for higher scoring ones, which is all the solver needs to tell whether
+    /// there are more solutions than it reports.
+    pub(crate) fn fill(
+        &self,
+        words: &WordList,
+        options: &AutofillOptions,
+        stop_when_full: bool,
+    ) -> AutofillResult {
+        let width = self.width as usize;
+        let mut letters: Vec<Option<u8>> = Vec::new();
+        for crossword_box in self.crossword_data.iter().flatten() {
+            letters.push(match crossword_box.value {
+                CrosswordBoxValue::Letter(letter) => Some(letter as u8),
+                _ => None,
+            });
+        }
+
+        let mut slots = Vec::new();
+        let mut locked_answers = Vec::new();
+        for entry in self.entries() {
+            // Word list words have one letter per box, so entries with a rebus are kept
+            let has_rebus = entry.cells.iter().any(|&(row, column)| {
+                matches!(
+                    self.crossword_data[row as usize][column as usize].value,
+                    CrosswordBoxValue::Rebus(_)
+                )
+            });
+            if has_rebus || options.locked.contains(&(entry.number, entry.direction)) {
+                locked_answers.push(entry.answer);
+                continue;
+            }
+            slots.push(
+                entry
+                    .cells
+                    .iter()
+                    .map(|(row, column)| *row as usize * width + *column as usize)
+                    .collect(),
+            );
+        }
+
+        // Every length gets a set of words that are still available
+        let mut available: Vec<Vec<u64>> = (0..=self.width.max(self.height) as usize)
+            .map(|length| {
+                words
+                    .index(length)
+                    .map(|index| index.all())
+                    .unwrap_or_default()
+            })
+            .collect();
+        for answer in &locked_answers {
+            if let Some(id) = words.id(answer) {
+                set_available(&mut available, words, id, false);
+            }
+        }
+
+        let mut rng = Rng::new(options.seed);
+        let jitter = (0..words.len()).map(|_| rng.next_u64()).collect();
+
+        let mut search = Search {
+            words,
+            slots,
+            letters,
+            assigned: Vec::new(),
+            available,
+            jitter,
+            steps: 0,
+            max_steps: options.max_steps,
+            deadline: options.time_limit.map(|limit| Instant::now() + limit),
+            results: options.results,
+            stop_when_full,
+            fills: BinaryHeap::new(),
+            found: 0,
+            stopped: false,
+        };
+        search.assigned = vec![None; search.slots.len()];
+
+        if options.results > 0 {
+            search.search();
+        }
+
+        let steps = search.steps;
+        let stopped = search.stopped;
+        let fills = search
+            .fills
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((score, _, letters))| {
+                let mut crossword = self.clone();
+                for (crossword_box, letter) in
+                    crossword.crossword_data.iter_mut().flatten().zip(letters)
+                {
+                    if let Some(letter) = letter {
+                        crossword_box.value = CrosswordBoxValue::Letter(letter as char);
+                    }
+                }
+                Fill { crossword, score }
+            })
+            .collect();
+
+        AutofillResult {
+            fills,
+            steps,
+            stopped,
+        }
+    }
+}
+
+/// A fill's score, then the order it was found in, which ranks earlier fills higher,
+/// then its letters.
+type Found = (u32, Reverse<u64>, Vec<Option<u8>>);
+
+struct Search<'a> {
+    words: &'a WordList,
+    /// Boxes of every entry that is filled, as indexes into `letters`.
+    slots: Vec<Vec<usize>>,
+    letters: Vec<Option<u8>>,
+    assigned: Vec<Option<usize>>,
+    available: Vec<Vec<u64>>,
+    jitter: Vec<u64>,
+    steps: u64,
+    max_steps: Option<u64>,
+    deadline: Option<Instant>,
+    results: usize,
+    stop_when_full: bool,
+    /// The best fills so far as a min-heap, so the worst one is dropped first.
+    fills: BinaryHeap<Reverse<Found>>,
+    found: u64,
+    stopped: bool,
+}
+
+impl Search<'_> {
+    fn out_of_budget(&self) -> bool {
+        self.max_steps.is_some_and(|max| self.steps >= max)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// The words that still fit a slot.
+    fn candidates(&self, slot: usize) -> Option<Vec<u64>> {
+        let cells = &self.slots[slot];
+        let index = self.words.index(cells.len())?;
+        let pattern: Vec<Option<u8>> = cells.iter().map(|&cell| self.letters[cell]).collect();
+
+        let mut candidates = self.available.get(cells.len())?.clone();
+        if index.restrict(&mut candidates, &pattern) {
+            Some(candidates)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true when the search should stop.
+    fn search(&mut self) -> bool {
+        if self.out_of_budget() {
+            self.stopped = true;
+            return true;
+        }
+
+        // The open slot with the fewest candidates, any slot without one is a dead end
+        let mut best: Option<(usize, Vec<u64>, u32)> = None;
+        for slot in 0..self.slots.len() {
+            if self.assigned[slot].is_some() {
+                continue;
+            }
+
+            let candidates = match self.candidates(slot) {
+                Some(candidates) => candidates,
+                None => return false,
+            };
+            let count = candidates.iter().map(|word| word.count_ones()).sum();
+            if best
+                .as_ref()
+                .map_or(true, |(_, _, best_count)| count < *best_count)
+            {
+                best = Some((slot, candidates, count));
+            }
+        }
+
+        let (slot, candidates) = match best {
+            Some((slot, candidates, _)) => (slot, candidates),
+            None => {
+                let score = self
+                    .assigned
+                    .iter()
+                    .flatten()
+                    .map(|&id| self.words.word_score(id))
+                    .sum();
+                self.fills
+                    .push(Reverse((score, Reverse(self.found), self.letters.clone())));
+                self.found += 1;
+                if self.fills.len() > self.results {
+                    self.fills.pop();
+                }
+                return self.stop_when_full && self.fills.len() == self.results;
+            }
+        };
+
+        let length = self.slots[slot].len();
+        let index = match self.words.index(length) {
+            Some(index) => index,
+            None => return false,
+        };
+        let mut ids: Vec<usize> = bits(&candidates)
+            .map(|position| index.words[position])
+            .collect();
+        ids.sort_by(|a, b| {
+            self.words
+                .word_score(*b)
+                .cmp(&self.words.word_score(*a))
+                .then(self.jitter[*a].cmp(&self.jitter[*b]))
+        });
+
+        for id in ids {
+            self.steps += 1;
+
+            let mut placed = Vec::new();
+            for (&cell, letter) in self.slots[slot].iter().zip(self.words.word(id).bytes()) {
+                if self.letters[cell].is_none() {
+                    self.letters[cell] = Some(letter);
+                    placed.push(cell);
+                }
+            }
+            self.assigned[slot] = Some(id);
+            set_available(&mut self.available, self.words, id, false);
+
+            let stop = self.search();
+
+            set_available(&mut self.available, self.words, id, true);
+            self.assigned[slot] = None;
+            for cell in placed {
+                self.letters[cell] = None;
+            }
+
+            if stop {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Marks a word as used or unused, so no word appears twice in a fill.
+fn set_available(available: &mut [Vec<u64>], words: &WordList, id: usize, value: bool) {
+    let length = words.word(id).len();
+    let position = words.position(id);
+
+    if let Some(word) = available
+        .get_mut(length)
+        .and_then(|set| set.get_mut(position / 64))
+    {
+        if value {
+            *word |= 1 << (position % 64);
+        } else {
+            *word &= !(1 << (position % 64));
+        }
+    }
+}
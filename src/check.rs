@@ -0,0 +1,181 @@
+//! Checks and reveals a player's guesses against the solution.
+//!
+//! Checking only reports which boxes are wrong, so a client never needs the letters of
+//! boxes the player hasn't revealed. Empty guesses and boxes without a letter in the
+//! solution are never reported as wrong. A rebus box is only right when the guess holds
+//! its whole content.
+//!
+//! In diagramless crosswords the solver places the blocks too. A block is wrong where
+//! the solution has a letter, and so is a letter where the solution has a block.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordData};
+use crate::entry::Direction;
+use crate::player::PlayerState;
+use crate::Error;
+
+impl PlayerState {
+    /// Checks the guess in a box, returning whether it's wrong.
+    pub fn check_cell(
+        &mut self,
+        crossword: &CrosswordData,
+        row: u8,
+        column: u8,
+    ) -> Result<bool, Error> {
+        self.ensure_matches(crossword)?;
+        self.check(crossword, row, column)
+    }
+
+    /// Checks the guesses in an entry, returning the boxes that are wrong.
+    pub fn check_entry(
+        &mut self,
+        crossword: &CrosswordData,
+        number: u8,
+        direction: Direction,
+    ) -> Result<Vec<(u8, u8)>, Error> {
+        self.ensure_matches(crossword)?;
+        let cells = entry_cells(crossword, number, direction)?;
+
+        self.check_cells(crossword, cells)
+    }
+
+    /// Checks every guess, returning the boxes that are wrong.
+    pub fn check_all(&mut self, crossword: &CrosswordData) -> Result<Vec<(u8, u8)>, Error> {
+        self.ensure_matches(crossword)?;
+        let cells = (0..crossword.height)
+            .flat_map(|row| (0..crossword.width).map(move |column| (row, column)))
+            .collect();
+
+        self.check_cells(crossword, cells)
+    }
+
+    /// Replaces the guess in a box with the solution.
+    pub fn reveal_cell(
+        &mut self,
+        crossword: &CrosswordData,
+        row: u8,
+        column: u8,
+    ) -> Result<(), Error> {
+        self.ensure_matches(crossword)?;
+        self.reveal(crossword, row, column)
+    }
+
+    /// Replaces the guesses in an entry with the solution.
+    pub fn reveal_entry(
+        &mut self,
+        crossword: &CrosswordData,
+        number: u8,
+        direction: Direction,
+    ) -> Result<(), Error> {
+        self.ensure_matches(crossword)?;
+
+        for (row, column) in entry_cells(crossword, number, direction)? {
+            self.reveal(crossword, row, column)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether every box with a letter in the solution has the right guess. Diagramless
+    /// crosswords also need a block in exactly the boxes that aren't open.
+    pub fn is_solved(&self, crossword: &CrosswordData) -> Result<bool, Error> {
+        self.ensure_matches(crossword)?;
+
+        let solved = crossword
+            .crossword_data
+            .iter()
+            .flatten()
+            .zip(self.cells.iter().flatten())
+            .all(
+                |(crossword_box, cell)| match crossword_box.value.content() {
+                    Some(content) => cell.guess == Some(content),
+                    None if crossword.diagramless => cell.block != crossword_box.value.is_open(),
+                    None => true,
+                },
+            );
+
+        Ok(solved)
+    }
+
+    fn ensure_matches(&self, crossword: &CrosswordData) -> Result<(), Error> {
+        if self.matches(crossword) {
+            Ok(())
+        } else {
+            Err(Error::PlayerStateMismatch)
+        }
+    }
+
+    fn check_cells(
+        &mut self,
+        crossword: &CrosswordData,
+        cells: Vec<(u8, u8)>,
+    ) -> Result<Vec<(u8, u8)>, Error> {
+        let mut wrong = Vec::new();
+        for (row, column) in cells {
+            if self.check(crossword, row, column)? {
+                wrong.push((row, column));
+            }
+        }
+
+        Ok(wrong)
+    }
+
+    fn check(&mut self, crossword: &CrosswordData, row: u8, column: u8) -> Result<bool, Error> {
+        let solution = solution(crossword, row, column)?;
+        let cell = self.cell_mut(row, column)?;
+
+        let wrong = match (cell.block, &cell.guess, solution.content()) {
+            (true, _, _) => solution.is_open(),
+            (false, Some(guess), Some(content)) => *guess != content,
+            // Only a diagramless solver can put a letter where the solution has a block
+            (false, Some(_), None) if crossword.diagramless => !solution.is_open(),
+            _ => return Ok(false),
+        };
+        cell.checked = true;
+
+        Ok(wrong)
+    }
+
+    fn reveal(&mut self, crossword: &CrosswordData, row: u8, column: u8) -> Result<(), Error> {
+        let solution = solution(crossword, row, column)?;
+        let cell = self.cell_mut(row, column)?;
+
+        match solution.content() {
+            Some(content) => {
+                cell.guess = Some(content);
+                cell.block = false;
+            }
+            None if crossword.diagramless && !solution.is_open() => {
+                cell.guess = None;
+                cell.block = true;
+            }
+            None => return Ok(()),
+        }
+        cell.pencil = false;
+        cell.revealed = true;
+
+        Ok(())
+    }
+}
+
+/// The value of a box in the solution.
+fn solution(crossword: &CrosswordData, row: u8, column: u8) -> Result<&CrosswordBoxValue, Error> {
+    crossword
+        .crossword_data
+        .get(row as usize)
+        .and_then(|boxes| boxes.get(column as usize))
+        .map(|crossword_box| &crossword_box.value)
+        .ok_or(Error::BoxOutOfRange { row, column })
+}
+
+fn entry_cells(
+    crossword: &CrosswordData,
+    number: u8,
+    direction: Direction,
+) -> Result<Vec<(u8, u8)>, Error> {
+    crossword
+        .entries()
+        .into_iter()
+        .find(|entry| entry.number == number && entry.direction == direction)
+        .map(|entry| entry.cells)
+        .ok_or(Error::EntryNotFound { number })
+}
@@ -1,8 +1,24 @@
 use crate::utils::extract_cstring_with_offset;
-use crate::Error;
-use std::usize;
+use crate::{Error, FORMAT_VERSION};
+use std::fmt;
 
-#[derive(Debug)]
+/// Box byte standing in for a rebus, whose content follows the grid.
+const REBUS: u8 = 0x01;
+const VOID: u8 = 0x02;
+
+const CIRCLED: u8 = 0b001;
+const SHADED: u8 = 0b010;
+const BOLD_BORDER: u8 = 0b100;
+
+const RIGHT_BAR: u8 = 0b01;
+const BOTTOM_BAR: u8 = 0b10;
+
+const DIAGRAMLESS: u8 = 0b1;
+
+const ACROSS_CLUE: u8 = 0;
+const DOWN_CLUE: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrosswordData {
     pub width: u8,
     pub height: u8,
@@ -10,10 +26,22 @@ pub struct CrosswordData {
     pub horizontal_clues: Vec<CrosswordClue>,
     pub vertical_clues: Vec<CrosswordClue>,
     pub crossword_data: Vec<Vec<CrosswordBox>>,
+    /// Solvers only get the dimensions and clues, and place the blocks themselves.
+    pub diagramless: bool,
 }
 
 impl CrosswordData {
+    /// Loads game data written by [`CrosswordData::to_bytes`]. Game data from a file,
+    /// which may be in an older version, is read by [`TggFile::from_bytes`].
+    ///
+    /// [`TggFile::from_bytes`]: crate::TggFile::from_bytes
     pub fn load(bytes: &[u8]) -> Result<CrosswordData, Error> {
+        CrosswordData::load_version(bytes, FORMAT_VERSION)
+    }
+
+    /// Loads game data written in the given format version, rejecting features that
+    /// version doesn't have.
+    pub(crate) fn load_version(bytes: &[u8], version: u8) -> Result<CrosswordData, Error> {
         let mut offset = 0;
         if bytes.len() < 3 {
             return Err(Error::UnexpectedEndOfFile);
@@ -58,35 +86,76 @@ impl CrosswordData {
             });
         };
 
-        let expected_bytes = offset as usize + (width * height) as usize * 2;
+        let grid_end = offset + width as usize * height as usize * 2;
 
         // Multiply the product of width and height by 2 to account for the number byte with every char
-        if expected_bytes != bytes.len() {
+        if grid_end > bytes.len() || (version < 3 && grid_end != bytes.len()) {
             return Err(Error::NotEnoughCrosswordBytes {
-                expected: expected_bytes as u32,
+                expected: grid_end as u32,
                 found: bytes.len() as u32,
             });
         }
         let mut crossword_data: Vec<Vec<CrosswordBox>> = Vec::new();
 
+        // Rebus contents follow the grid, in grid order
+        let mut rebus_offset = grid_end;
         for _ in 0..height {
             let mut row: Vec<CrosswordBox> = Vec::new();
             for _ in 0..width {
                 let number = bytes[offset];
-                let value = match CrosswordBoxValue::from_byte(bytes[offset + 1]) {
-                    Ok(value) => value,
-                    Err(err) => return Err(err),
-                };
-                let crossword_box = match CrosswordBox::new(number, value) {
-                    Ok(crossword_box) => crossword_box,
-                    Err(err) => return Err(err),
+                let value = match bytes[offset + 1] {
+                    REBUS if version >= 3 => {
+                        if rebus_offset >= bytes.len() {
+                            return Err(Error::UnexpectedEndOfFile);
+                        }
+                        let (rebus, new_offset) = extract_cstring_with_offset(bytes, rebus_offset);
+                        rebus_offset = new_offset;
+                        CrosswordBoxValue::Rebus(rebus)
+                    }
+                    VOID if version < 6 => {
+                        return Err(Error::InvalidCrosswordBoxByte { found: VOID })
+                    }
+                    byte => CrosswordBoxValue::from_byte(byte)?,
                 };
+                let crossword_box = CrosswordBox::new(number, value)?;
                 row.push(crossword_box);
                 offset += 2;
             }
             crossword_data.push(row);
         }
 
+        // Styles follow the rebus contents. Version 4 leaves them out when no box has
+        // one, later versions always have them followed by the bars.
+        let mut end = rebus_offset;
+        if version >= 5 || (version == 4 && end < bytes.len()) {
+            end = load_styles(bytes, end, &mut crossword_data)?;
+        }
+        if version >= 5 {
+            end = load_bars(bytes, end, &mut crossword_data)?;
+        }
+
+        // Version 7 ends with the puzzle flags
+        let mut diagramless = false;
+        if version >= 7 {
+            let flags = *bytes.get(end).ok_or(Error::UnexpectedEndOfFile)?;
+            if flags & !DIAGRAMLESS != 0 {
+                return Err(Error::InvalidPuzzleFlags { found: flags });
+            }
+            diagramless = flags & DIAGRAMLESS != 0;
+            end += 1;
+        }
+
+        if version >= 8 {
+            end = load_enumerations(bytes, end, &mut horizontal_clues, &mut vertical_clues)?;
+        }
+
+        if end != bytes.len() {
+            return Err(Error::NotEnoughCrosswordBytes {
+                expected: end as u32,
+                found: bytes.len() as u32,
+            });
+        }
+
         Ok(CrosswordData {
             width,
             height,
@@ -94,6 +163,7 @@ impl CrosswordData {
             horizontal_clues,
             vertical_clues,
             crossword_data,
+            diagramless,
         })
     }
 
@@ -179,10 +249,94 @@ impl CrosswordData {
             vertical_clues,
             horizontal_clues,
             crossword_data,
+            diagramless: false,
         })
     }
 
+    /// Creates a crossword from its boxes alone, numbering every box that starts an
+    /// entry. Clues refer to the assigned numbers.
+    pub fn from_grid(
+        width: u8,
+        height: u8,
+        horizontal_clues: Vec<CrosswordClue>,
+        vertical_clues: Vec<CrosswordClue>,
+        grid: Vec<Vec<CrosswordBoxValue>>,
+    ) -> Result<CrosswordData, Error> {
+        let mut crossword_data = Vec::new();
+        for row in grid {
+            let mut boxes = Vec::new();
+            for value in row {
+                boxes.push(CrosswordBox::new(0, value)?);
+            }
+            crossword_data.push(boxes);
+        }
+
+        // Numbers have to be in place before `new` checks the clues against them
+        let mut numbered = CrosswordData {
+            width,
+            height,
+            total_clues: 0,
+            horizontal_clues: Vec::new(),
+            vertical_clues: Vec::new(),
+            crossword_data,
+            diagramless: false,
+        };
+        numbered.auto_number()?;
+
+        CrosswordData::new(
+            width,
+            height,
+            horizontal_clues,
+            vertical_clues,
+            numbered.crossword_data,
+        )
+    }
+
+    /// Replaces the number of every box with standard crossword numbering. Clues are
+    /// left as they are.
+    pub fn auto_number(&mut self) -> Result<(), Error> {
+        let numbers = standard_numbering(&self.crossword_data)?;
+
+        for (boxes, row_numbers) in self.crossword_data.iter_mut().zip(numbers) {
+            for (crossword_box, number) in boxes.iter_mut().zip(row_numbers) {
+                crossword_box.number = number;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that exactly the boxes starting an entry are numbered, counting up from 1
+    /// left to right and top to bottom. Returns the first box that differs.
+    pub fn validate_numbering(&self) -> Result<(), Error> {
+        let numbers = standard_numbering(&self.crossword_data)?;
+
+        for (row, boxes) in self.crossword_data.iter().enumerate() {
+            for (column, crossword_box) in boxes.iter().enumerate() {
+                let expected = numbers[row][column];
+                if crossword_box.number != expected {
+                    return Err(Error::NumberMismatch {
+                        row: row as u8,
+                        column: column as u8,
+                        expected,
+                        found: crossword_box.number,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The game data in the latest format version, which [`CrosswordData::load`] reads.
+    /// Files store the lowest version that holds the crossword instead.
     pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_version(FORMAT_VERSION)
+    }
+
+    /// The game data as written in the given format version, which has to be at least
+    /// [`CrosswordData::format_version`].
+    pub(crate) fn to_bytes_version(&self, version: u8) -> Vec<u8> {
         let mut bytes = Vec::new();
 
         bytes.push(self.width);
@@ -207,14 +361,151 @@ impl CrosswordData {
             }
         }
 
-        return bytes;
+        for item in self.crossword_data.iter().flatten() {
+            if let CrosswordBoxValue::Rebus(rebus) = &item.value {
+                bytes.extend(rebus.as_bytes());
+                bytes.push(0);
+            }
+        }
+
+        let styled: Vec<(usize, usize, &CellStyle)> = self
+            .crossword_data
+            .iter()
+            .enumerate()
+            .flat_map(|(row, items)| {
+                items
+                    .iter()
+                    .enumerate()
+                    .map(move |(column, item)| (row, column, &item.style))
+            })
+            .filter(|(_, _, style)| !style.is_plain())
+            .collect();
+        if version >= 4 {
+            bytes.extend((styled.len() as u16).to_le_bytes());
+            for (row, column, style) in styled {
+                bytes.extend(style.to_bytes(row as u8, column as u8));
+            }
+        }
+
+        if version >= 5 {
+            let barred: Vec<(usize, usize, Bars)> = self
+                .crossword_data
+                .iter()
+                .enumerate()
+                .flat_map(|(row, items)| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(move |(column, item)| (row, column, item.bars))
+                })
+                .filter(|(_, _, bars)| bars.any())
+                .collect();
+            bytes.extend((barred.len() as u16).to_le_bytes());
+            for (row, column, bars) in barred {
+                bytes.extend([row as u8, column as u8, bars.to_byte()]);
+            }
+        }
+
+        if version >= 7 {
+            bytes.push(if self.diagramless { DIAGRAMLESS } else { 0 });
+        }
+
+        if version >= 8 {
+            let enumerated: Vec<(u8, &CrosswordClue, &Enumeration)> = [
+                (ACROSS_CLUE, &self.horizontal_clues),
+                (DOWN_CLUE, &self.vertical_clues),
+            ]
+            .into_iter()
+            .flat_map(|(direction, clues)| {
+                clues.iter().filter_map(move |clue| {
+                    clue.enumeration
+                        .as_ref()
+                        .map(|enumeration| (direction, clue, enumeration))
+                })
+            })
+            .collect();
+            bytes.extend((enumerated.len() as u16).to_le_bytes());
+            for (direction, clue, enumeration) in enumerated {
+                bytes.extend([direction, clue.number]);
+                bytes.extend(enumeration.to_string().as_bytes());
+                bytes.push(0);
+            }
+        }
+
+        bytes
+    }
+
+    /// Whether the grid has void boxes, so it isn't a plain rectangle.
+    pub fn is_shaped(&self) -> bool {
+        self.crossword_data
+            .iter()
+            .flatten()
+            .any(|item| item.value == CrosswordBoxValue::Void)
+    }
+
+    /// The grid a diagramless solver starts from, with every box empty and unnumbered
+    /// and no bars. Styles of open boxes are kept.
+    pub fn without_diagram(&self) -> CrosswordData {
+        let crossword_data = self
+            .crossword_data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|item| CrosswordBox {
+                        number: 0,
+                        value: CrosswordBoxValue::Empty,
+                        style: if item.value.is_open() {
+                            item.style
+                        } else {
+                            CellStyle::default()
+                        },
+                        bars: Bars::default(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        CrosswordData {
+            width: self.width,
+            height: self.height,
+            total_clues: self.total_clues,
+            horizontal_clues: self.horizontal_clues.clone(),
+            vertical_clues: self.vertical_clues.clone(),
+            crossword_data,
+            diagramless: false,
+        }
+    }
+
+    /// Lowest format version that can store the crossword.
+    pub(crate) fn format_version(&self) -> u8 {
+        let items = || self.crossword_data.iter().flatten();
+
+        let clues = || self.horizontal_clues.iter().chain(&self.vertical_clues);
+
+        if clues().any(|clue| clue.enumeration.is_some()) {
+            8
+        } else if self.diagramless {
+            7
+        } else if self.is_shaped() {
+            6
+        } else if items().any(|item| item.bars.any()) {
+            5
+        } else if items().any(|item| !item.style.is_plain()) {
+            4
+        } else if items().any(|item| matches!(item.value, CrosswordBoxValue::Rebus(_))) {
+            3
+        } else {
+            1
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrosswordClue {
     pub number: u8,
     pub value: String,
+    /// Word lengths of the answer, as given in cryptic clues.
+    pub enumeration: Option<Enumeration>,
 }
 
 impl CrosswordClue {
@@ -222,6 +513,36 @@ impl CrosswordClue {
         CrosswordClue {
             number,
             value: value.to_string(),
+            enumeration: None,
+        }
+    }
+
+    pub fn with_enumeration(mut self, enumeration: Enumeration) -> CrosswordClue {
+        self.enumeration = Some(enumeration);
+        self
+    }
+
+    /// Reads a clue that may end with its enumeration, like `Beaten eggs (8)`. Text
+    /// without an enumeration at the end is kept as it is.
+    pub fn from_full_text(number: u8, text: &str) -> CrosswordClue {
+        let trimmed = text.trim_end();
+        if trimmed.ends_with(')') {
+            if let Some(open) = trimmed.rfind('(') {
+                if let Ok(enumeration) = Enumeration::parse(&trimmed[open..]) {
+                    return CrosswordClue::new(number, trimmed[..open].trim_end())
+                        .with_enumeration(enumeration);
+                }
+            }
+        }
+
+        CrosswordClue::new(number, text)
+    }
+
+    /// The clue text followed by its enumeration in parentheses, if it has one.
+    pub fn full_text(&self) -> String {
+        match &self.enumeration {
+            Some(enumeration) => format!("{} ({})", self.value, enumeration),
+            None => self.value.clone(),
         }
     }
 
@@ -235,15 +556,90 @@ impl CrosswordClue {
     }
 }
 
-#[derive(Debug, Clone)]
+/// The lengths of the words in an answer and what separates them, written like `5,3`
+/// or `4-4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enumeration {
+    pub words: Vec<u8>,
+    /// What follows each word but the last.
+    pub separators: Vec<Separator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    Space,
+    Hyphen,
+}
+
+impl Enumeration {
+    /// Parses an enumeration with or without parentheses. Words are separated by a
+    /// comma or a space, or joined by a hyphen.
+    pub fn parse(text: &str) -> Result<Enumeration, Error> {
+        let invalid = || Error::InvalidEnumeration {
+            enumeration: text.to_string(),
+        };
+
+        let trimmed = text.trim();
+        let inner = trimmed
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+            .unwrap_or(trimmed)
+            .replace(", ", ",");
+
+        let length = |word: &str| word.parse::<u8>().ok().filter(|_| !word.starts_with('0'));
+        let mut words = Vec::new();
+        let mut separators = Vec::new();
+        let mut word = String::new();
+        for c in inner.chars() {
+            match c {
+                '0'..='9' => word.push(c),
+                ',' | ' ' | '-' => {
+                    words.push(length(&word).ok_or_else(invalid)?);
+                    separators.push(match c {
+                        '-' => Separator::Hyphen,
+                        _ => Separator::Space,
+                    });
+                    word.clear();
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        words.push(length(&word).ok_or_else(invalid)?);
+
+        Ok(Enumeration { words, separators })
+    }
+
+    /// Number of letters in the answer.
+    pub fn length(&self) -> usize {
+        self.words.iter().map(|&word| word as usize).sum()
+    }
+}
+
+impl fmt::Display for Enumeration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, word) in self.words.iter().enumerate() {
+            write!(f, "{}", word)?;
+            match self.separators.get(index) {
+                Some(Separator::Space) => write!(f, ",")?,
+                Some(Separator::Hyphen) => write!(f, "-")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrosswordBox {
     pub number: u8,
     pub value: CrosswordBoxValue,
+    pub style: CellStyle,
+    pub bars: Bars,
 }
 
 impl CrosswordBox {
     pub fn new(number: u8, value: CrosswordBoxValue) -> Result<CrosswordBox, Error> {
-        match value {
+        match &value {
             CrosswordBoxValue::Letter(value) => {
                 if !value.is_ascii() {
                     return Err(Error::NonAsciiCharacter);
@@ -257,42 +653,132 @@ impl CrosswordBox {
                     return Err(Error::NonUppercaseCharacter);
                 }
             }
+            CrosswordBoxValue::Rebus(rebus) => validate_rebus(rebus)?,
             _ => {}
         }
 
-        Ok(CrosswordBox { number, value })
+        Ok(CrosswordBox {
+            number,
+            value,
+            style: CellStyle::default(),
+            bars: Bars::default(),
+        })
+    }
+
+    pub fn with_style(mut self, style: CellStyle) -> CrosswordBox {
+        self.style = style;
+        self
+    }
+
+    pub fn with_bars(mut self, bars: Bars) -> CrosswordBox {
+        self.bars = bars;
+        self
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.push(self.number);
-        bytes.push(self.value.to_byte());
+        vec![self.number, self.value.to_byte()]
+    }
+}
+
+/// How a box is highlighted, usually to mark theme letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    pub circled: bool,
+    /// Background color as red, green and blue.
+    pub shade: Option<[u8; 3]>,
+    pub bold_border: bool,
+}
 
-        return bytes;
+impl CellStyle {
+    /// Whether the box has no highlighting at all.
+    pub fn is_plain(&self) -> bool {
+        *self == CellStyle::default()
+    }
+
+    fn to_bytes(self, row: u8, column: u8) -> Vec<u8> {
+        let mut flags = 0;
+        if self.circled {
+            flags |= CIRCLED;
+        }
+        if self.shade.is_some() {
+            flags |= SHADED;
+        }
+        if self.bold_border {
+            flags |= BOLD_BORDER;
+        }
+
+        let mut bytes = vec![row, column, flags];
+        if let Some(shade) = self.shade {
+            bytes.extend(shade);
+        }
+
+        bytes
+    }
+}
+
+/// Thick bars on the edges of a box, which end entries like a solid box does. Barred
+/// grids often have no solid boxes at all. Bars on the left and top edge are stored on
+/// the neighbouring box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bars {
+    pub right: bool,
+    pub bottom: bool,
+}
+
+impl Bars {
+    pub fn any(&self) -> bool {
+        self.right || self.bottom
+    }
+
+    fn to_byte(self) -> u8 {
+        let mut flags = 0;
+        if self.right {
+            flags |= RIGHT_BAR;
+        }
+        if self.bottom {
+            flags |= BOTTOM_BAR;
+        }
+
+        flags
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CrosswordBoxValue {
     Empty,
     Solid,
+    /// Not part of the puzzle at all, for grids that aren't rectangular. Unlike a solid
+    /// box it isn't drawn.
+    Void,
     Letter(char),
+    /// Several letters, digits or symbols in one box.
+    Rebus(String),
 }
 
 impl CrosswordBoxValue {
+    /// The byte stored in the grid. A rebus is stored as a placeholder byte, its
+    /// content is written after the grid.
     pub fn to_byte(&self) -> u8 {
         match self {
             CrosswordBoxValue::Empty => 0x20, // ASCII for space
             CrosswordBoxValue::Solid => 0x23, // ASCII for #
+            CrosswordBoxValue::Void => VOID,
             CrosswordBoxValue::Letter(letter) => *letter as u8,
+            CrosswordBoxValue::Rebus(_) => REBUS,
         }
     }
 
-    pub fn to_string(&self) -> String {
+    /// Whether the box can hold a letter, so it's neither solid nor void.
+    pub fn is_open(&self) -> bool {
+        !matches!(self, CrosswordBoxValue::Solid | CrosswordBoxValue::Void)
+    }
+
+    /// What the box holds in the solution, if anything.
+    pub fn content(&self) -> Option<String> {
         match self {
-            CrosswordBoxValue::Empty => " ".to_string(),
-            CrosswordBoxValue::Solid => "#".to_string(),
-            CrosswordBoxValue::Letter(letter) => letter.to_string(),
+            CrosswordBoxValue::Letter(letter) => Some(letter.to_string()),
+            CrosswordBoxValue::Rebus(rebus) => Some(rebus.clone()),
+            _ => None,
         }
     }
 
@@ -300,12 +786,233 @@ impl CrosswordBoxValue {
         match byte {
             0x20 => Ok(CrosswordBoxValue::Empty), // ASCII for space
             0x23 => Ok(CrosswordBoxValue::Solid), // ASCII for #
+            VOID => Ok(CrosswordBoxValue::Void),
             b if b.is_ascii_alphabetic() => Ok(CrosswordBoxValue::Letter(b as char)),
             _ => Err(Error::InvalidCrosswordBoxByte { found: byte }),
         }
     }
 }
 
+impl fmt::Display for CrosswordBoxValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrosswordBoxValue::Empty => write!(f, " "),
+            CrosswordBoxValue::Solid => write!(f, "#"),
+            CrosswordBoxValue::Void => write!(f, " "),
+            CrosswordBoxValue::Letter(letter) => write!(f, "{}", letter),
+            CrosswordBoxValue::Rebus(rebus) => write!(f, "{}", rebus),
+        }
+    }
+}
+
+/// Rebus content is printable ASCII without spaces or lowercase letters.
+pub(crate) fn validate_rebus(rebus: &str) -> Result<(), Error> {
+    if rebus.is_empty() {
+        return Err(Error::RebusIsEmpty);
+    }
+
+    for c in rebus.chars() {
+        if !c.is_ascii() {
+            return Err(Error::NonAsciiCharacter);
+        }
+
+        if !c.is_ascii_graphic() {
+            return Err(Error::InvalidRebusCharacter { found: c });
+        }
+
+        if c.is_lowercase() {
+            return Err(Error::NonUppercaseCharacter);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_open(grid: &[Vec<CrosswordBox>], row: usize, column: usize) -> bool {
+    grid.get(row)
+        .and_then(|cells| cells.get(column))
+        .is_some_and(|cell| cell.value.is_open())
+}
+
+/// Whether an entry continues from the box into the box to its right.
+pub(crate) fn joins_right(grid: &[Vec<CrosswordBox>], row: usize, column: usize) -> bool {
+    is_open(grid, row, column) && !grid[row][column].bars.right && is_open(grid, row, column + 1)
+}
+
+/// Whether an entry continues from the box into the box below it.
+pub(crate) fn joins_below(grid: &[Vec<CrosswordBox>], row: usize, column: usize) -> bool {
+    is_open(grid, row, column) && !grid[row][column].bars.bottom && is_open(grid, row + 1, column)
+}
+
+/// Whether the box starts a horizontal entry of at least two boxes.
+pub(crate) fn starts_horizontal_entry(
+    grid: &[Vec<CrosswordBox>],
+    row: usize,
+    column: usize,
+) -> bool {
+    (column == 0 || !joins_right(grid, row, column - 1)) && joins_right(grid, row, column)
+}
+
+/// Whether the box starts a vertical entry of at least two boxes.
+pub(crate) fn starts_vertical_entry(grid: &[Vec<CrosswordBox>], row: usize, column: usize) -> bool {
+    (row == 0 || !joins_below(grid, row - 1, column)) && joins_below(grid, row, column)
+}
+
+/// Numbers every box that starts an entry, left to right and top to bottom.
+/// Boxes that don't start an entry get 0.
+pub(crate) fn standard_numbering(grid: &[Vec<CrosswordBox>]) -> Result<Vec<Vec<u8>>, Error> {
+    let mut next_number: u32 = 1;
+    let mut numbers = Vec::new();
+
+    for (row, cells) in grid.iter().enumerate() {
+        let mut row_numbers = Vec::new();
+        for column in 0..cells.len() {
+            if starts_horizontal_entry(grid, row, column)
+                || starts_vertical_entry(grid, row, column)
+            {
+                if next_number > u8::MAX as u32 {
+                    return Err(Error::TooManyEntries);
+                }
+                row_numbers.push(next_number as u8);
+                next_number += 1;
+            } else {
+                row_numbers.push(0);
+            }
+        }
+        numbers.push(row_numbers);
+    }
+
+    Ok(numbers)
+}
+
+/// Reads the style section: a count, then the row, column and flags of every styled box,
+/// followed by its color when it's shaded. Returns the offset after the section.
+fn load_styles(
+    bytes: &[u8],
+    mut offset: usize,
+    crossword_data: &mut [Vec<CrosswordBox>],
+) -> Result<usize, Error> {
+    let count = match bytes.get(offset..offset + 2) {
+        Some(count) => u16::from_le_bytes([count[0], count[1]]),
+        None => return Err(Error::UnexpectedEndOfFile),
+    };
+    offset += 2;
+
+    for _ in 0..count {
+        let (row, column, flags) = match bytes.get(offset..offset + 3) {
+            Some(style) => (style[0], style[1], style[2]),
+            None => return Err(Error::UnexpectedEndOfFile),
+        };
+        offset += 3;
+
+        if flags & !(CIRCLED | SHADED | BOLD_BORDER) != 0 {
+            return Err(Error::InvalidCellStyleFlags { found: flags });
+        }
+
+        let shade = if flags & SHADED != 0 {
+            let shade = match bytes.get(offset..offset + 3) {
+                Some(shade) => [shade[0], shade[1], shade[2]],
+                None => return Err(Error::UnexpectedEndOfFile),
+            };
+            offset += 3;
+            Some(shade)
+        } else {
+            None
+        };
+
+        let crossword_box = crossword_data
+            .get_mut(row as usize)
+            .and_then(|boxes| boxes.get_mut(column as usize))
+            .ok_or(Error::BoxOutOfRange { row, column })?;
+        crossword_box.style = CellStyle {
+            circled: flags & CIRCLED != 0,
+            shade,
+            bold_border: flags & BOLD_BORDER != 0,
+        };
+    }
+
+    Ok(offset)
+}
+
+/// Reads the bar section: a count, then the row, column and bar flags of every barred box.
+/// Returns the offset after the section.
+fn load_bars(
+    bytes: &[u8],
+    mut offset: usize,
+    crossword_data: &mut [Vec<CrosswordBox>],
+) -> Result<usize, Error> {
+    let count = match bytes.get(offset..offset + 2) {
+        Some(count) => u16::from_le_bytes([count[0], count[1]]),
+        None => return Err(Error::UnexpectedEndOfFile),
+    };
+    offset += 2;
+
+    for _ in 0..count {
+        let (row, column, flags) = match bytes.get(offset..offset + 3) {
+            Some(bars) => (bars[0], bars[1], bars[2]),
+            None => return Err(Error::UnexpectedEndOfFile),
+        };
+        offset += 3;
+
+        if flags & !(RIGHT_BAR | BOTTOM_BAR) != 0 {
+            return Err(Error::InvalidBarFlags { found: flags });
+        }
+
+        let crossword_box = crossword_data
+            .get_mut(row as usize)
+            .and_then(|boxes| boxes.get_mut(column as usize))
+            .ok_or(Error::BoxOutOfRange { row, column })?;
+        crossword_box.bars = Bars {
+            right: flags & RIGHT_BAR != 0,
+            bottom: flags & BOTTOM_BAR != 0,
+        };
+    }
+
+    Ok(offset)
+}
+
+/// Reads the enumeration section: a count, then the direction and number of every clue
+/// with an enumeration followed by the enumeration as text.
+fn load_enumerations(
+    bytes: &[u8],
+    mut offset: usize,
+    horizontal_clues: &mut [CrosswordClue],
+    vertical_clues: &mut [CrosswordClue],
+) -> Result<usize, Error> {
+    let count = match bytes.get(offset..offset + 2) {
+        Some(count) => u16::from_le_bytes([count[0], count[1]]),
+        None => return Err(Error::UnexpectedEndOfFile),
+    };
+    offset += 2;
+
+    for _ in 0..count {
+        let (direction, number) = match bytes.get(offset..offset + 2) {
+            Some(clue) => (clue[0], clue[1]),
+            None => return Err(Error::UnexpectedEndOfFile),
+        };
+        if offset + 2 >= bytes.len() {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+        let (text, new_offset) = extract_cstring_with_offset(bytes, offset + 2);
+        offset = new_offset;
+
+        let clue = match direction {
+            ACROSS_CLUE => horizontal_clues
+                .iter_mut()
+                .find(|clue| clue.number == number)
+                .ok_or(Error::HorizontalClueContainsInvalidNumber { number })?,
+            DOWN_CLUE => vertical_clues
+                .iter_mut()
+                .find(|clue| clue.number == number)
+                .ok_or(Error::VerticalClueContainsInvalidNumber { number })?,
+            _ => return Err(Error::InvalidClueDirection { found: direction }),
+        };
+        clue.enumeration = Some(Enumeration::parse(&text)?);
+    }
+
+    Ok(offset)
+}
+
 fn parse_crossword_clue(bytes: &[u8], start: usize) -> (CrosswordClue, usize) {
     let number = bytes[start];
     let (value, end_offset) = extract_cstring_with_offset(bytes, start + 1); // Start after the clue number
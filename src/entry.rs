@@ -0,0 +1,212 @@
+//! Entries, the runs of boxes that clues are answered in.
+
+use crate::crossword::{
+    starts_horizontal_entry, starts_vertical_entry, CrosswordBoxValue, CrosswordClue, CrosswordData,
+};
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub number: u8,
+    pub direction: Direction,
+    /// Row and column of the first box.
+    pub start: (u8, u8),
+    pub length: u8,
+    /// Row and column of every box, in reading order.
+    pub cells: Vec<(u8, u8)>,
+    /// The solution letters, with `?` for boxes that are still empty. Rebus boxes add
+    /// all of their content, so the answer can be longer than `length`.
+    pub answer: String,
+}
+
+impl CrosswordData {
+    /// Every entry of at least two boxes, across entries first and each direction in
+    /// grid order. Entries take the number of their first box.
+    pub fn entries(&self) -> Vec<Entry> {
+        let grid = &self.crossword_data;
+        let mut across = Vec::new();
+        let mut down = Vec::new();
+
+        for (row, boxes) in grid.iter().enumerate() {
+            for column in 0..boxes.len() {
+                if starts_horizontal_entry(grid, row, column) {
+                    across.push(self.walk_entry(row, column, Direction::Across));
+                }
+                if starts_vertical_entry(grid, row, column) {
+                    down.push(self.walk_entry(row, column, Direction::Down));
+                }
+            }
+        }
+
+        across.extend(down);
+        across
+    }
+
+    /// The entry a clue is answered in. The direction comes from the clue list that
+    /// contains the clue.
+    pub fn entry_for_clue(&self, clue: &CrosswordClue) -> Option<Entry> {
+        let direction = if self.horizontal_clues.contains(clue) {
+            Direction::Across
+        } else if self.vertical_clues.contains(clue) {
+            Direction::Down
+        } else {
+            return None;
+        };
+
+        self.entries()
+            .into_iter()
+            .find(|entry| entry.number == clue.number && entry.direction == direction)
+    }
+
+    /// The entries that cross the box, at most one in each direction.
+    pub fn entries_at(&self, row: u8, column: u8) -> Vec<Entry> {
+        self.entries()
+            .into_iter()
+            .filter(|entry| entry.cells.contains(&(row, column)))
+            .collect()
+    }
+
+    /// Checks that every entry has exactly one clue in the list for its direction, and
+    /// that every clue belongs to an entry. Every problem found is returned, entries
+    /// first.
+    pub fn validate_clues(&self) -> Result<(), Vec<Error>> {
+        let entries = self.entries();
+        let mut errors = Vec::new();
+
+        for entry in &entries {
+            let clues = match entry.direction {
+                Direction::Across => &self.horizontal_clues,
+                Direction::Down => &self.vertical_clues,
+            };
+            let count = clues
+                .iter()
+                .filter(|clue| clue.number == entry.number)
+                .count() as u32;
+
+            let number = entry.number;
+            match (entry.direction, count) {
+                (_, 1) => {}
+                (Direction::Across, 0) => errors.push(Error::MissingHorizontalClue { number }),
+                (Direction::Down, 0) => errors.push(Error::MissingVerticalClue { number }),
+                (Direction::Across, _) => {
+                    errors.push(Error::RepeatedHorizontalClue { number, count })
+                }
+                (Direction::Down, _) => errors.push(Error::RepeatedVerticalClue { number, count }),
+            }
+        }
+
+        let has_entry = |number: u8, direction: Direction| {
+            entries
+                .iter()
+                .any(|entry| entry.number == number && entry.direction == direction)
+        };
+
+        for clue in &self.horizontal_clues {
+            if !has_entry(clue.number, Direction::Across) {
+                errors.push(Error::HorizontalClueWithoutEntry {
+                    number: clue.number,
+                });
+            }
+        }
+
+        for clue in &self.vertical_clues {
+            if !has_entry(clue.number, Direction::Down) {
+                errors.push(Error::VerticalClueWithoutEntry {
+                    number: clue.number,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks that every enumeration adds up to the number of letters in the entry of its
+    /// clue. Rebus boxes count all of their letters.
+    pub fn validate_enumerations(&self) -> Result<(), Error> {
+        for entry in self.entries() {
+            let clues = match entry.direction {
+                Direction::Across => &self.horizontal_clues,
+                Direction::Down => &self.vertical_clues,
+            };
+
+            for clue in clues.iter().filter(|clue| clue.number == entry.number) {
+                let enumeration = match &clue.enumeration {
+                    Some(enumeration) => enumeration,
+                    None => continue,
+                };
+
+                let number = entry.number;
+                let expected = entry.answer.chars().count() as u32;
+                let found = enumeration.length() as u32;
+                if expected != found {
+                    return Err(match entry.direction {
+                        Direction::Across => Error::HorizontalEnumerationMismatch {
+                            number,
+                            expected,
+                            found,
+                        },
+                        Direction::Down => Error::VerticalEnumerationMismatch {
+                            number,
+                            expected,
+                            found,
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn walk_entry(&self, row: usize, column: usize, direction: Direction) -> Entry {
+        let mut cells = Vec::new();
+        let mut answer = String::new();
+        let (mut current_row, mut current_column) = (row, column);
+
+        while let Some(crossword_box) = self
+            .crossword_data
+            .get(current_row)
+            .and_then(|boxes| boxes.get(current_column))
+        {
+            match &crossword_box.value {
+                CrosswordBoxValue::Solid | CrosswordBoxValue::Void => break,
+                CrosswordBoxValue::Empty => answer.push('?'),
+                CrosswordBoxValue::Letter(letter) => answer.push(*letter),
+                CrosswordBoxValue::Rebus(rebus) => answer.push_str(rebus),
+            }
+            cells.push((current_row as u8, current_column as u8));
+
+            let barred = match direction {
+                Direction::Across => crossword_box.bars.right,
+                Direction::Down => crossword_box.bars.bottom,
+            };
+            if barred {
+                break;
+            }
+
+            match direction {
+                Direction::Across => current_column += 1,
+                Direction::Down => current_row += 1,
+            }
+        }
+
+        Entry {
+            number: self.crossword_data[row][column].number,
+            direction,
+            start: (row as u8, column as u8),
+            length: cells.len() as u8,
+            cells,
+            answer,
+        }
+    }
+}
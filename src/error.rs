@@ -1,6 +1,7 @@
 #[derive(Debug)]
 pub enum Error {
     // -- Load
+    Io(std::io::Error),
     InsufficientHeaderBytes { min: u32, found: u32 },
     InvalidID,
     InvalidGameTypeByte { found: u8 },
@@ -11,6 +12,9 @@ pub enum Error {
     HeaderChecksumMismatch { expected: u16, found: u16 },
     FooterChecksumMismatch { expected: u16, found: u16 },
     GameDataIsEmpty,
+    UnsupportedFormatVersion { found: u8 },
+    NotACrossword,
+    NotAWordSearch,
     // -- Crossword
     UnexpectedEndOfFile,
     WidthOrHeightIsZero,
@@ -28,6 +32,60 @@ pub enum Error {
     NonAlphabeticCharacter,
     NonUppercaseCharacter,
     InvalidCrosswordBoxByte { found: u8 },
+    TooManyEntries,
+    NumberMismatch { row: u8, column: u8, expected: u8, found: u8 },
+    MissingHorizontalClue { number: u8 },
+    MissingVerticalClue { number: u8 },
+    RepeatedHorizontalClue { number: u8, count: u32 },
+    RepeatedVerticalClue { number: u8, count: u32 },
+    HorizontalClueWithoutEntry { number: u8 },
+    VerticalClueWithoutEntry { number: u8 },
+    EmptyBoxInSolution { row: u8, column: u8 },
+    RebusIsEmpty,
+    InvalidRebusCharacter { found: char },
+    InvalidCellStyleFlags { found: u8 },
+    InvalidBarFlags { found: u8 },
+    InvalidPuzzleFlags { found: u8 },
+    InvalidClueDirection { found: u8 },
+    InvalidEnumeration { enumeration: String },
+    HorizontalEnumerationMismatch { number: u8, expected: u32, found: u32 },
+    VerticalEnumerationMismatch { number: u8, expected: u32, found: u32 },
+    // -- Word Search
+    NotEnoughWordSearchBytes { expected: u32, found: u32 },
+    HeightWordSearchDataMismatch { height: u8, grid_height: u32 },
+    WidthWordSearchDataMismatch { width: u8, grid_width: u32 },
+    TooManyWords,
+    WordIsEmpty,
+    // -- Puz
+    InvalidPuzMagic,
+    PuzSolutionScrambled,
+    PuzClueCountMismatch { expected: u16, found: u16 },
+    PuzBarsUnsupported,
+    PuzTooManyRebuses,
+    // -- Ipuz
+    JsonSyntax { offset: u32, reason: String },
+    UnsupportedIpuzKind { kind: String },
+    IpuzMissingField { field: String },
+    IpuzInvalidField { field: String },
+    // -- Jpz
+    XmlSyntax { offset: u32, reason: String },
+    UnsupportedJpzPuzzle,
+    JpzMissingElement { name: String },
+    JpzMissingAttribute { name: String },
+    JpzInvalidAttribute { name: String },
+    // -- Text
+    TextSyntax { line: u32, column: u32, reason: String },
+    // -- Word List
+    InvalidWordListScore { line: u32 },
+    // -- Generators
+    NoWordsPlaced,
+    // -- Player State
+    NotEnoughPlayerStateBytes { expected: u32, found: u32 },
+    InvalidPlayerGuessByte { found: u8 },
+    InvalidPlayerFlags { found: u8 },
+    BoxOutOfRange { row: u8, column: u8 },
+    PlayerStateMismatch,
+    EntryNotFound { number: u8 },
 }
 
 impl core::fmt::Display for Error {
@@ -0,0 +1,165 @@
+//! Self-contained HTML export for solving crosswords in a browser.
+//!
+//! The page needs no network access, the player script and styles are inlined and the
+//! puzzle is embedded as JSON. The solution is XORed with a key stream so it can't be
+//! read straight from the page source, which keeps honest solvers honest but is not
+//! meant to stop anyone determined.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordClue, CrosswordData};
+use crate::json::Json;
+use crate::utils::hex_color;
+use crate::{Error, GameData, TggFile};
+
+const STYLE: &str = include_str!("html/player.css");
+const SCRIPT: &str = include_str!("html/player.js");
+
+impl TggFile {
+    pub fn to_html(&self) -> Result<String, Error> {
+        let crossword = match &self.gamedata {
+            GameData::Crossword(crossword) => crossword,
+            _ => return Err(Error::NotACrossword),
+        };
+
+        let key = self.metadata.gamedata_checksum;
+        let data = puzzle_json(crossword, key).to_pretty_string();
+
+        let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        html.push_str(&format!(
+            "<title>{}</title>\n",
+            escape(&self.metadata.title)
+        ));
+        html.push_str(&format!("<style>\n{}</style>\n", STYLE));
+        html.push_str("</head>\n<body>\n<header>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", escape(&self.metadata.title)));
+        html.push_str(&format!(
+            "<p class=\"byline\">By {} &middot; {}</p>\n",
+            escape(&self.metadata.author),
+            escape(&self.metadata.get_date())
+        ));
+        if !self.metadata.description.is_empty() {
+            html.push_str(&format!(
+                "<p class=\"description\">{}</p>\n",
+                escape(&self.metadata.description)
+            ));
+        }
+        html.push_str("</header>\n<main id=\"puzzle\"></main>\n");
+        // `</` can't appear inside a script element, JSON allows escaping the slash
+        html.push_str(&format!(
+            "<script type=\"application/json\" id=\"puzzle-data\">\n{}\n</script>\n",
+            data.replace("</", "<\\/")
+        ));
+        html.push_str(&format!("<script>\n{}</script>\n", SCRIPT));
+        html.push_str("</body>\n</html>\n");
+
+        Ok(html)
+    }
+}
+
+fn puzzle_json(crossword: &CrosswordData, key: u16) -> Json {
+    let mut cells = Vec::new();
+    let mut solution = String::new();
+    let mut rebuses = Vec::new();
+    let mut styles = Vec::new();
+    let mut bars = Vec::new();
+    let mut voids = Vec::new();
+
+    // Diagramless solvers only see an empty grid, the blocks are left in the solution
+    let hidden = crossword.diagramless.then(|| crossword.without_diagram());
+    let shown = hidden.as_ref().unwrap_or(crossword);
+    let boxes = crossword
+        .crossword_data
+        .iter()
+        .flatten()
+        .zip(shown.crossword_data.iter().flatten());
+
+    for (index, (crossword_box, shown_box)) in boxes.enumerate() {
+        let open = shown_box.value.is_open();
+        if shown_box.value == CrosswordBoxValue::Void {
+            voids.push(Json::Number(index as f64));
+        }
+        cells.push(Json::Array(vec![
+            Json::Number(shown_box.number as f64),
+            Json::Bool(open),
+        ]));
+
+        // Rebus boxes hold their first character here and their full content separately
+        let content = crossword_box.value.content().unwrap_or_default();
+        let first = content.bytes().next().unwrap_or(0);
+        solution.push_str(&format!("{:02x}", first ^ key_stream(key, index)));
+
+        if let CrosswordBoxValue::Rebus(rebus) = &crossword_box.value {
+            let hex: String = rebus
+                .bytes()
+                .enumerate()
+                .map(|(offset, byte)| format!("{:02x}", byte ^ key_stream(key, index + offset)))
+                .collect();
+            rebuses.push(Json::Array(vec![
+                Json::Number(index as f64),
+                Json::String(hex),
+            ]));
+        }
+
+        let style = shown_box.style;
+        if !style.is_plain() {
+            styles.push(Json::Array(vec![
+                Json::Number(index as f64),
+                Json::Bool(style.circled),
+                Json::Bool(style.bold_border),
+                style
+                    .shade
+                    .map_or(Json::Null, |shade| Json::String(hex_color(shade))),
+            ]));
+        }
+
+        if shown_box.bars.any() {
+            bars.push(Json::Array(vec![
+                Json::Number(index as f64),
+                Json::Bool(shown_box.bars.right),
+                Json::Bool(shown_box.bars.bottom),
+            ]));
+        }
+    }
+
+    Json::Object(vec![
+        ("width".to_string(), Json::Number(crossword.width as f64)),
+        ("height".to_string(), Json::Number(crossword.height as f64)),
+        ("key".to_string(), Json::Number(key as f64)),
+        ("diagramless".to_string(), Json::Bool(crossword.diagramless)),
+        ("cells".to_string(), Json::Array(cells)),
+        ("solution".to_string(), Json::String(solution)),
+        ("rebus".to_string(), Json::Array(rebuses)),
+        ("styles".to_string(), Json::Array(styles)),
+        ("bars".to_string(), Json::Array(bars)),
+        ("void".to_string(), Json::Array(voids)),
+        ("across".to_string(), clues(&crossword.horizontal_clues)),
+        ("down".to_string(), clues(&crossword.vertical_clues)),
+    ])
+}
+
+/// Must match `keyStream` in the player script.
+fn key_stream(key: u16, index: usize) -> u8 {
+    ((key as usize + index * 31) & 0xff) as u8
+}
+
+fn clues(clues: &[CrosswordClue]) -> Json {
+    Json::Array(
+        clues
+            .iter()
+            .map(|clue| {
+                Json::Array(vec![
+                    Json::Number(clue.number as f64),
+                    Json::String(clue.full_text()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,523 @@
+(function () {
+  "use strict";
+
+  var data = JSON.parse(document.getElementById("puzzle-data").textContent);
+  var width = data.width;
+  var height = data.height;
+  var count = width * height;
+
+  var guesses = [];
+  var states = [];
+  // Blocks placed by the solver of a diagramless puzzle, and the numbers they give
+  var blocks = [];
+  var numbers = [];
+  var elements = [];
+  var selected = -1;
+  var direction = "across";
+  var rebusMode = false;
+  var rebuses = {};
+  var styles = {};
+  var bars = {};
+
+  data.rebus.forEach(function (rebus) {
+    rebuses[rebus[0]] = rebus[1];
+  });
+  // Circled, bold border and shade color, by box
+  data.styles.forEach(function (style) {
+    styles[style[0]] = style;
+  });
+  // Right and bottom bars, by box
+  data.bars.forEach(function (bar) {
+    bars[bar[0]] = bar;
+  });
+
+  // Must match `key_stream` in html.rs
+  function keyStream(index) {
+    return (data.key + index * 31) & 255;
+  }
+
+  function decode(hex, index) {
+    var text = "";
+    for (var offset = 0; offset * 2 < hex.length; offset++) {
+      var code = parseInt(hex.substr(offset * 2, 2), 16) ^ keyStream(index + offset);
+      text += code ? String.fromCharCode(code) : "";
+    }
+    return text;
+  }
+
+  function solutionAt(index) {
+    if (rebuses.hasOwnProperty(index)) {
+      return decode(rebuses[index], index);
+    }
+    return decode(data.solution.substr(index * 2, 2), index);
+  }
+
+  function isOpen(row, column) {
+    return row >= 0 && column >= 0 && row < height && column < width &&
+      data.cells[row * width + column][1] && !blocks[row * width + column];
+  }
+
+  // Whether an entry continues from the box to the next one, which a bar prevents
+  function joins(row, column, dir) {
+    var rowStep = dir === "across" ? 0 : 1;
+    var columnStep = dir === "across" ? 1 : 0;
+    var bar = bars[row * width + column];
+    var barred = bar && (dir === "across" ? bar[1] : bar[2]);
+    return isOpen(row, column) && !barred && isOpen(row + rowStep, column + columnStep);
+  }
+
+  function entryCells(index, dir) {
+    var row = Math.floor(index / width);
+    var column = index % width;
+    var rowStep = dir === "across" ? 0 : 1;
+    var columnStep = dir === "across" ? 1 : 0;
+    var cells = [row * width + column];
+
+    while (joins(row - rowStep, column - columnStep, dir)) {
+      row -= rowStep;
+      column -= columnStep;
+      cells.unshift(row * width + column);
+    }
+    row = Math.floor(index / width);
+    column = index % width;
+    while (joins(row, column, dir)) {
+      row += rowStep;
+      column += columnStep;
+      cells.push(row * width + column);
+    }
+
+    return cells;
+  }
+
+  function currentEntry() {
+    return selected < 0 ? [] : entryCells(selected, direction);
+  }
+
+  function numberAt(index) {
+    return data.diagramless ? numbers[index] : data.cells[index][0];
+  }
+
+  // Diagramless grids are numbered from the blocks the solver placed so far
+  function renumber() {
+    var next = 1;
+    for (var i = 0; i < count; i++) {
+      var row = Math.floor(i / width);
+      var column = i % width;
+      var across = !joins(row, column - 1, "across") && joins(row, column, "across");
+      var down = !joins(row - 1, column, "down") && joins(row, column, "down");
+      numbers[i] = across || down ? next++ : 0;
+    }
+  }
+
+  function clueFor(cells, dir) {
+    if (cells.length < 2) {
+      return null;
+    }
+    var number = numberAt(cells[0]);
+    for (var i = 0; i < data[dir].length; i++) {
+      if (data[dir][i][0] === number) {
+        return data[dir][i];
+      }
+    }
+    return null;
+  }
+
+  var root = document.getElementById("puzzle");
+  var board = document.createElement("div");
+  var clueBar = document.createElement("div");
+  var grid = document.createElement("div");
+  var controls = document.createElement("div");
+  var message = document.createElement("div");
+  var clueLists = document.createElement("div");
+  var clueItems = { across: {}, down: {} };
+
+  clueBar.className = "current-clue";
+  // Grids with void boxes take the shape of their boxes instead of a rectangle
+  grid.className = data.void.length ? "grid shaped" : "grid";
+  grid.tabIndex = 0;
+  grid.style.gridTemplateColumns = "repeat(" + width + ", 36px)";
+  controls.className = "controls";
+  message.className = "message";
+  clueLists.className = "clues";
+
+  for (var index = 0; index < count; index++) {
+    var cell = document.createElement("div");
+    guesses.push("");
+    states.push("");
+    blocks.push(false);
+    numbers.push(0);
+
+    if (data.cells[index][1]) {
+      cell.className = "cell";
+      if (data.cells[index][0] || data.diagramless) {
+        var number = document.createElement("span");
+        number.className = "number";
+        number.textContent = data.cells[index][0] || "";
+        cell.appendChild(number);
+      }
+      var letter = document.createElement("span");
+      letter.className = "letter";
+      cell.appendChild(letter);
+      cell.addEventListener("click", onCellClick.bind(null, index));
+      if (selected < 0) {
+        selected = index;
+      }
+    } else if (data.void.indexOf(index) >= 0) {
+      cell.className = "cell void";
+    } else {
+      cell.className = "cell solid";
+    }
+
+    elements.push(cell);
+    grid.appendChild(cell);
+  }
+
+  ["across", "down"].forEach(function (dir) {
+    var section = document.createElement("section");
+    var title = document.createElement("h2");
+    var list = document.createElement("ol");
+
+    title.textContent = dir === "across" ? "Across" : "Down";
+    data[dir].forEach(function (clue) {
+      var item = document.createElement("li");
+      var number = document.createElement("b");
+      number.textContent = clue[0];
+      item.appendChild(number);
+      item.appendChild(document.createTextNode(" " + clue[1]));
+      item.addEventListener("click", function () {
+        selectClue(dir, clue[0]);
+      });
+      clueItems[dir][clue[0]] = item;
+      list.appendChild(item);
+    });
+
+    section.appendChild(title);
+    section.appendChild(list);
+    clueLists.appendChild(section);
+  });
+
+  [
+    ["Check Square", function () { check([selected]); }],
+    ["Check Word", function () { check(currentEntry()); }],
+    ["Check Puzzle", function () { check(allCells()); }],
+    ["Reveal Square", function () { reveal([selected]); }],
+    ["Reveal Word", function () { reveal(currentEntry()); }],
+    ["Reveal Puzzle", function () { reveal(allCells()); }],
+    ["Rebus", toggleRebus],
+    ["Clear", clear]
+  ].forEach(function (control) {
+    var button = document.createElement("button");
+    button.type = "button";
+    button.textContent = control[0];
+    button.addEventListener("click", function () {
+      control[1]();
+      update();
+      grid.focus();
+    });
+    controls.appendChild(button);
+  });
+
+  board.appendChild(clueBar);
+  board.appendChild(grid);
+  board.appendChild(controls);
+  board.appendChild(message);
+  root.appendChild(board);
+  root.appendChild(clueLists);
+
+  function allCells() {
+    var cells = [];
+    for (var i = 0; i < count; i++) {
+      if (data.cells[i][1]) {
+        cells.push(i);
+      }
+    }
+    return cells;
+  }
+
+  // Only diagramless solvers place blocks, where the solution has no letter
+  function isBlock(index) {
+    return data.diagramless && solutionAt(index) === "";
+  }
+
+  function check(cells) {
+    cells.forEach(function (i) {
+      if (i < 0) {
+        return;
+      }
+      var wrong = blocks[i] ? !isBlock(i) : guesses[i] && guesses[i] !== solutionAt(i);
+      if (wrong) {
+        states[i] = "wrong";
+      }
+    });
+  }
+
+  function reveal(cells) {
+    cells.forEach(function (i) {
+      if (i >= 0 && (guesses[i] !== solutionAt(i) || blocks[i] !== isBlock(i))) {
+        guesses[i] = solutionAt(i);
+        blocks[i] = isBlock(i);
+        states[i] = "revealed";
+      }
+    });
+  }
+
+  function clear() {
+    for (var i = 0; i < count; i++) {
+      guesses[i] = "";
+      states[i] = "";
+      blocks[i] = false;
+    }
+  }
+
+  function isSolved() {
+    return allCells().every(function (i) {
+      return guesses[i] === solutionAt(i) && blocks[i] === isBlock(i);
+    });
+  }
+
+  function onCellClick(index) {
+    if (index === selected) {
+      toggleDirection();
+    } else {
+      selected = index;
+      if (entryCells(selected, direction).length < 2) {
+        toggleDirection();
+      }
+    }
+    update();
+    grid.focus();
+  }
+
+  function toggleDirection() {
+    var other = direction === "across" ? "down" : "across";
+    if (entryCells(selected, other).length > 1) {
+      direction = other;
+    }
+  }
+
+  function selectClue(dir, number) {
+    for (var i = 0; i < count; i++) {
+      if (isOpen(Math.floor(i / width), i % width) && numberAt(i) === number &&
+        entryCells(i, dir)[0] === i && entryCells(i, dir).length > 1) {
+        selected = i;
+        direction = dir;
+        break;
+      }
+    }
+    update();
+    grid.focus();
+  }
+
+  // Moves to the next or previous clue, wrapping from across to down
+  function stepClue(offset) {
+    var clues = data.across.map(function (clue) { return ["across", clue[0]]; })
+      .concat(data.down.map(function (clue) { return ["down", clue[0]]; }));
+    var current = clueFor(currentEntry(), direction);
+    var position = 0;
+
+    for (var i = 0; i < clues.length; i++) {
+      if (current && clues[i][0] === direction && clues[i][1] === current[0]) {
+        position = (i + offset + clues.length) % clues.length;
+      }
+    }
+    if (clues.length) {
+      selectClue(clues[position][0], clues[position][1]);
+    }
+  }
+
+  function move(rowStep, columnStep) {
+    var row = Math.floor(selected / width) + rowStep;
+    var column = selected % width + columnStep;
+
+    while (row >= 0 && column >= 0 && row < height && column < width) {
+      // Placed blocks can be selected, so they can be taken out again
+      if (data.cells[row * width + column][1]) {
+        selected = row * width + column;
+        return;
+      }
+      row += rowStep;
+      column += columnStep;
+    }
+  }
+
+  function enter(value) {
+    var entry = currentEntry();
+    var position = entry.indexOf(selected);
+
+    if (states[selected] !== "revealed") {
+      guesses[selected] = value;
+      states[selected] = "";
+      blocks[selected] = false;
+    }
+    if (position >= 0 && position + 1 < entry.length) {
+      selected = entry[position + 1];
+    }
+  }
+
+  function toggleBlock() {
+    if (states[selected] !== "revealed") {
+      blocks[selected] = !blocks[selected];
+      guesses[selected] = "";
+      states[selected] = "";
+    }
+    if (direction === "across") {
+      move(0, 1);
+    } else {
+      move(1, 0);
+    }
+  }
+
+  // In rebus mode typed characters are added to the selected box until it's turned off
+  function toggleRebus() {
+    if (rebusMode) {
+      rebusMode = false;
+      enter(guesses[selected]);
+    } else if (states[selected] !== "revealed" && !blocks[selected]) {
+      rebusMode = true;
+      guesses[selected] = "";
+      states[selected] = "";
+    }
+  }
+
+  function erase() {
+    var entry = currentEntry();
+    var position = entry.indexOf(selected);
+
+    if (!guesses[selected] && position > 0) {
+      selected = entry[position - 1];
+    }
+    if (states[selected] !== "revealed") {
+      guesses[selected] = "";
+      states[selected] = "";
+    }
+  }
+
+  grid.addEventListener("keydown", function (event) {
+    if (selected < 0 || event.ctrlKey || event.metaKey || event.altKey) {
+      return;
+    }
+
+    var key = event.key;
+    if (rebusMode) {
+      if (/^[!-~]$/.test(key)) {
+        guesses[selected] += key.toUpperCase();
+      } else if (key === "Backspace") {
+        guesses[selected] = guesses[selected].slice(0, -1);
+      } else if (key === "Enter" || key === "Escape" || key === "Insert") {
+        toggleRebus();
+      } else {
+        return;
+      }
+      event.preventDefault();
+      update();
+      return;
+    }
+
+    if (key === "Insert") {
+      toggleRebus();
+    } else if (key === "." && data.diagramless) {
+      toggleBlock();
+    } else if (/^[a-zA-Z]$/.test(key)) {
+      enter(key.toUpperCase());
+    } else if (key === "Backspace" || key === "Delete") {
+      erase();
+    } else if (key === "ArrowLeft" || key === "ArrowRight") {
+      if (direction !== "across" && entryCells(selected, "across").length > 1) {
+        direction = "across";
+      } else {
+        move(0, key === "ArrowLeft" ? -1 : 1);
+      }
+    } else if (key === "ArrowUp" || key === "ArrowDown") {
+      if (direction !== "down" && entryCells(selected, "down").length > 1) {
+        direction = "down";
+      } else {
+        move(key === "ArrowUp" ? -1 : 1, 0);
+      }
+    } else if (key === " ") {
+      toggleDirection();
+    } else if (key === "Tab" || key === "Enter") {
+      stepClue(event.shiftKey ? -1 : 1);
+    } else {
+      return;
+    }
+
+    event.preventDefault();
+    update();
+  });
+
+  function update() {
+    if (data.diagramless) {
+      renumber();
+    }
+    var entry = currentEntry();
+    var clue = clueFor(entry, direction);
+
+    for (var i = 0; i < count; i++) {
+      if (!data.cells[i][1]) {
+        continue;
+      }
+      var classes = ["cell"];
+      var highlighted = true;
+      if (i === selected) {
+        classes.push(rebusMode ? "selected rebus-entry" : "selected");
+      } else if (entry.indexOf(i) >= 0) {
+        classes.push("entry");
+      } else {
+        highlighted = false;
+      }
+      if (blocks[i]) {
+        classes.push("block");
+      }
+      if (states[i]) {
+        classes.push(states[i]);
+      }
+      var style = styles[i];
+      if (style && style[1]) {
+        classes.push("circled");
+      }
+      if (style && style[2]) {
+        classes.push("bold");
+      }
+      var bar = bars[i];
+      if (bar && bar[1]) {
+        classes.push("bar-right");
+      }
+      if (bar && bar[2]) {
+        classes.push("bar-bottom");
+      }
+      // The highlight wins over the shade so the current entry stays visible
+      var shaded = style && style[3] && !highlighted && !blocks[i];
+      elements[i].style.backgroundColor = shaded ? style[3] : "";
+      elements[i].className = classes.join(" ");
+      var letter = elements[i].querySelector(".letter");
+      letter.textContent = guesses[i];
+      letter.className = guesses[i].length > 1 ? "letter rebus" : "letter";
+      if (data.diagramless) {
+        elements[i].querySelector(".number").textContent = numbers[i] || "";
+      }
+    }
+
+    ["across", "down"].forEach(function (dir) {
+      Object.keys(clueItems[dir]).forEach(function (number) {
+        var active = clue && dir === direction && Number(number) === clue[0];
+        clueItems[dir][number].className = active ? "active" : "";
+        if (active && clueItems[dir][number].scrollIntoView) {
+          clueItems[dir][number].scrollIntoView({ block: "nearest" });
+        }
+      });
+    });
+
+    clueBar.textContent = clue
+      ? clue[0] + (direction === "across" ? " Across: " : " Down: ") + clue[1]
+      : "";
+    message.textContent = isSolved() ? "Solved!" : "";
+  }
+
+  if (data.diagramless) {
+    renumber();
+  }
+  if (selected >= 0 && entryCells(selected, direction).length < 2) {
+    toggleDirection();
+  }
+  update();
+})();
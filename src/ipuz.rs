@@ -0,0 +1,646 @@
+//! ipuz (JSON) import and export for crosswords and word searches.
+//!
+//! Top level fields and clue directions that can't be represented are kept as a JSON
+//! object in the `ipuz` extension, and written back out on export. Box styles and bars
+//! use the `shapebg`, `color`, `border` and `barred` properties of a cell style.
+//! Diagramless crosswords add the diagramless crossword kind. Clues with an enumeration
+//! are written as objects with an `enumeration` field.
+
+use crate::crossword::{
+    validate_rebus, Bars, CellStyle, CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData,
+    Enumeration,
+};
+use crate::json::Json;
+use crate::utils::{hex_color, parse_hex_color};
+use crate::word_search::WordSearchData;
+use crate::{current_timestamp, imported_metadata, Error, Extension, GameData, TggFile, Warning};
+
+const VERSION: &str = "http://ipuz.org/v2";
+const CROSSWORD_KIND: &str = "http://ipuz.org/crossword";
+const DIAGRAMLESS_KIND: &str = "http://ipuz.org/crossword/diagramless";
+const WORD_SEARCH_KIND: &str = "http://ipuz.org/wordsearch";
+const EXTENSION_KEY: &str = "ipuz";
+const DEFAULT_BLOCK: &str = "#";
+
+impl TggFile {
+    pub fn from_ipuz(text: &str) -> Result<(TggFile, Vec<Warning>), Error> {
+        parse(text)
+    }
+
+    pub fn to_ipuz(&self) -> String {
+        let mut fields = vec![
+            ("version".to_string(), Json::String(VERSION.to_string())),
+            ("kind".to_string(), Json::Array(Vec::new())),
+            ("title".to_string(), string(&self.metadata.title)),
+            ("author".to_string(), string(&self.metadata.author)),
+            ("intro".to_string(), string(&self.metadata.description)),
+        ];
+
+        let preserved = self
+            .get_extension(EXTENSION_KEY)
+            .and_then(|value| Json::parse(value).ok())
+            .and_then(|value| value.as_object().cloned())
+            .unwrap_or_default();
+
+        match &self.gamedata {
+            GameData::Crossword(crossword) => {
+                let mut kinds = vec![string(&format!("{}#1", CROSSWORD_KIND))];
+                if crossword.diagramless {
+                    kinds.push(string(&format!("{}#1", DIAGRAMLESS_KIND)));
+                }
+                fields[1].1 = Json::Array(kinds);
+                write_crossword(crossword, &preserved, &mut fields);
+            }
+            GameData::WordSearch(word_search) => {
+                fields[1].1 = Json::Array(vec![string(&format!("{}#1", WORD_SEARCH_KIND))]);
+                write_word_search(word_search, &mut fields);
+            }
+        }
+
+        for (key, value) in preserved {
+            if !fields.iter().any(|(field, _)| *field == key) {
+                fields.push((key, value));
+            }
+        }
+
+        Json::Object(fields).to_pretty_string()
+    }
+}
+
+fn parse(text: &str) -> Result<(TggFile, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+    let json = Json::parse(text)?;
+
+    let fields = match json.as_object() {
+        Some(fields) => fields,
+        None => return Err(invalid_field("root")),
+    };
+
+    match json.get("version").and_then(Json::as_str) {
+        Some(version) if version.starts_with("http://ipuz.org/v") => {}
+        Some(_) => return Err(invalid_field("version")),
+        None => return Err(missing_field("version")),
+    }
+
+    let kinds = match json.get("kind").and_then(Json::as_array) {
+        Some(kinds) => kinds,
+        None => return Err(missing_field("kind")),
+    };
+    let kinds: Vec<&str> = kinds.iter().filter_map(Json::as_str).collect();
+
+    let dimensions = match json.get("dimensions") {
+        Some(dimensions) => dimensions,
+        None => return Err(missing_field("dimensions")),
+    };
+    let width = dimension(dimensions, "width")?;
+    let height = dimension(dimensions, "height")?;
+
+    let mut consumed = vec![
+        "version",
+        "kind",
+        "dimensions",
+        "title",
+        "author",
+        "intro",
+        "puzzle",
+        "solution",
+    ];
+    let mut preserved_clues = Vec::new();
+
+    let gamedata = if kinds.iter().any(|kind| kind.starts_with(CROSSWORD_KIND)) {
+        consumed.extend(["block", "empty", "clues"]);
+        let mut crossword =
+            parse_crossword(&json, width, height, &mut preserved_clues, &mut warnings)?;
+        crossword.diagramless = kinds.iter().any(|kind| kind.starts_with(DIAGRAMLESS_KIND));
+        GameData::Crossword(crossword)
+    } else if kinds.iter().any(|kind| kind.starts_with(WORD_SEARCH_KIND)) {
+        GameData::WordSearch(parse_word_search(&json, width, height)?)
+    } else {
+        return Err(Error::UnsupportedIpuzKind {
+            kind: kinds.first().unwrap_or(&"").to_string(),
+        });
+    };
+
+    let mut preserved: Vec<(String, Json)> = fields
+        .iter()
+        .filter(|(key, _)| !consumed.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    if !preserved_clues.is_empty() {
+        preserved.push(("clues".to_string(), Json::Object(preserved_clues)));
+    }
+
+    let mut extensions = Vec::new();
+    if !preserved.is_empty() {
+        extensions.push(Extension::new(
+            EXTENSION_KEY,
+            &Json::Object(preserved).to_pretty_string(),
+        ));
+    }
+
+    let text_field = |key: &str| {
+        json.get(key)
+            .and_then(Json::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let title = imported_metadata(text_field("title"), "title", &mut warnings);
+    let description = imported_metadata(text_field("intro"), "description", &mut warnings);
+    let author = imported_metadata(text_field("author"), "author", &mut warnings);
+
+    let tgg_file = TggFile::assemble(
+        &title,
+        &description,
+        &author,
+        current_timestamp(),
+        gamedata,
+        extensions,
+    );
+
+    Ok((tgg_file, warnings))
+}
+
+fn parse_crossword(
+    json: &Json,
+    width: u8,
+    height: u8,
+    preserved_clues: &mut Vec<(String, Json)>,
+    warnings: &mut Vec<Warning>,
+) -> Result<CrosswordData, Error> {
+    let block = json
+        .get("block")
+        .and_then(Json::as_str)
+        .unwrap_or(DEFAULT_BLOCK)
+        .to_string();
+    let empty = match json.get("empty") {
+        Some(Json::String(empty)) => empty.clone(),
+        Some(Json::Number(empty)) => format!("{}", *empty as i64),
+        _ => "0".to_string(),
+    };
+
+    let puzzle = grid(json, "puzzle", width, height)?.ok_or_else(|| missing_field("puzzle"))?;
+    let solution = grid(json, "solution", width, height)?;
+
+    let mut crossword_data: Vec<Vec<CrosswordBox>> = Vec::new();
+    for row in 0..height as usize {
+        let mut cells: Vec<CrosswordBox> = Vec::new();
+        for column in 0..width as usize {
+            let position = (row as u8, column as u8);
+            let (number, shape) =
+                parse_puzzle_cell(&puzzle[row][column], &block, &empty, position, warnings)?;
+            let (style, barred) = parse_style(&puzzle[row][column], position, warnings);
+            let bars = Bars {
+                right: barred.contains('R'),
+                bottom: barred.contains('B'),
+            };
+            // Left and top bars belong to the neighbouring box
+            if barred.contains('L') && column > 0 {
+                cells[column - 1].bars.right = true;
+            }
+            if barred.contains('T') && row > 0 {
+                crossword_data[row - 1][column].bars.bottom = true;
+            }
+
+            let value = match solution.as_ref() {
+                Some(solution) => {
+                    parse_solution_cell(&solution[row][column], &block, position, warnings)
+                }
+                None => CrosswordBoxValue::Empty,
+            };
+
+            let value = shape.unwrap_or(value);
+            cells.push(
+                CrosswordBox::new(number, value)?
+                    .with_style(style)
+                    .with_bars(bars),
+            );
+        }
+        crossword_data.push(cells);
+    }
+
+    let mut horizontal_clues = Vec::new();
+    let mut vertical_clues = Vec::new();
+
+    if let Some(clues) = json.get("clues") {
+        let directions = match clues.as_object() {
+            Some(directions) => directions,
+            None => return Err(invalid_field("clues")),
+        };
+
+        for (direction, list) in directions {
+            let target = match direction.split(':').next().unwrap_or_default() {
+                "Across" => &mut horizontal_clues,
+                "Down" => &mut vertical_clues,
+                _ => {
+                    preserved_clues.push((direction.clone(), list.clone()));
+                    continue;
+                }
+            };
+
+            let list = match list.as_array() {
+                Some(list) => list,
+                None => return Err(invalid_field("clues")),
+            };
+
+            for clue in list {
+                if let Some(clue) = parse_clue(clue, warnings)? {
+                    target.push(clue);
+                }
+            }
+        }
+    }
+
+    if horizontal_clues.len() + vertical_clues.len() > u8::MAX as usize {
+        return Err(Error::TooManyEntries);
+    }
+
+    CrosswordData::new(
+        width,
+        height,
+        horizontal_clues,
+        vertical_clues,
+        crossword_data,
+    )
+}
+
+/// Returns the box number, and the value for blocks and omitted cells, which are void.
+fn parse_puzzle_cell(
+    cell: &Json,
+    block: &str,
+    empty: &str,
+    (row, column): (u8, u8),
+    warnings: &mut Vec<Warning>,
+) -> Result<(u8, Option<CrosswordBoxValue>), Error> {
+    match cell {
+        Json::Null => Ok((0, Some(CrosswordBoxValue::Void))),
+        Json::Number(_) => match cell.as_u64() {
+            Some(number) if number <= u8::MAX as u64 => Ok((number as u8, None)),
+            Some(_) => Err(Error::TooManyEntries),
+            None => Err(invalid_field("puzzle")),
+        },
+        Json::String(value) if value == block => Ok((0, Some(CrosswordBoxValue::Solid))),
+        Json::String(value) if value == empty || value.is_empty() => Ok((0, None)),
+        Json::String(value) => match value.parse::<u64>() {
+            Ok(number) if number <= u8::MAX as u64 => Ok((number as u8, None)),
+            Ok(_) => Err(Error::TooManyEntries),
+            Err(_) => {
+                warnings.push(Warning::IpuzCellIgnored { row, column });
+                Ok((0, None))
+            }
+        },
+        Json::Object(fields) => {
+            if fields
+                .iter()
+                .any(|(key, _)| key != "cell" && key != "style")
+            {
+                warnings.push(Warning::IpuzCellIgnored { row, column });
+            }
+            match cell.get("cell") {
+                Some(inner) => parse_puzzle_cell(inner, block, empty, (row, column), warnings),
+                None => Ok((0, None)),
+            }
+        }
+        _ => Err(invalid_field("puzzle")),
+    }
+}
+
+/// Reads the circle, shade, border and barred sides from the style of a puzzle cell.
+/// Named styles and other style properties are ignored.
+fn parse_style(
+    cell: &Json,
+    (row, column): (u8, u8),
+    warnings: &mut Vec<Warning>,
+) -> (CellStyle, String) {
+    let mut style = CellStyle::default();
+    let mut barred = String::new();
+    let fields = match cell.get("style") {
+        Some(Json::Object(fields)) => fields,
+        Some(_) => {
+            warnings.push(Warning::IpuzCellIgnored { row, column });
+            return (style, barred);
+        }
+        None => return (style, barred),
+    };
+
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            ("shapebg", Json::String(shape)) if shape == "circle" => style.circled = true,
+            ("color", Json::String(color)) if parse_hex_color(color).is_some() => {
+                style.shade = parse_hex_color(color)
+            }
+            // A plain border is 1, anything thicker is bold
+            ("border", Json::Number(_)) => style.bold_border = value.as_u64() > Some(1),
+            ("barred", Json::String(sides)) if sides.chars().all(|side| "TRBL".contains(side)) => {
+                barred = sides.clone()
+            }
+            _ => warnings.push(Warning::IpuzCellIgnored { row, column }),
+        }
+    }
+
+    (style, barred)
+}
+
+fn parse_solution_cell(
+    cell: &Json,
+    block: &str,
+    (row, column): (u8, u8),
+    warnings: &mut Vec<Warning>,
+) -> CrosswordBoxValue {
+    match cell {
+        Json::String(value) if value == block => CrosswordBoxValue::Solid,
+        Json::String(value) => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (None, _) => CrosswordBoxValue::Empty,
+                (Some(letter), None) if letter.is_ascii_alphabetic() => {
+                    CrosswordBoxValue::Letter(letter.to_ascii_uppercase())
+                }
+                (Some(_), Some(_)) => {
+                    let rebus = value.to_ascii_uppercase();
+                    if validate_rebus(&rebus).is_ok() {
+                        CrosswordBoxValue::Rebus(rebus)
+                    } else {
+                        warnings.push(Warning::IpuzRebusIgnored { row, column });
+                        CrosswordBoxValue::Empty
+                    }
+                }
+                (Some(found), None) => {
+                    warnings.push(Warning::UnsupportedCharacter { row, column, found });
+                    CrosswordBoxValue::Empty
+                }
+            }
+        }
+        Json::Object(_) => match cell.get("value") {
+            Some(value) => parse_solution_cell(value, block, (row, column), warnings),
+            None => CrosswordBoxValue::Empty,
+        },
+        _ => CrosswordBoxValue::Empty,
+    }
+}
+
+fn parse_clue(clue: &Json, warnings: &mut Vec<Warning>) -> Result<Option<CrosswordClue>, Error> {
+    let (number, text) = match clue {
+        Json::Array(values) if values.len() == 2 => (&values[0], &values[1]),
+        Json::Object(fields) => {
+            if fields
+                .iter()
+                .any(|(key, _)| !["number", "clue", "enumeration"].contains(&key.as_str()))
+            {
+                warnings.push(Warning::IpuzFieldIgnored {
+                    field: "clues".to_string(),
+                });
+            }
+            match (clue.get("number"), clue.get("clue")) {
+                (Some(number), Some(text)) => (number, text),
+                _ => return Err(invalid_field("clues")),
+            }
+        }
+        Json::String(_) => {
+            warnings.push(Warning::IpuzFieldIgnored {
+                field: "clues".to_string(),
+            });
+            return Ok(None);
+        }
+        _ => return Err(invalid_field("clues")),
+    };
+
+    let number = match number {
+        Json::Number(_) => number.as_u64(),
+        Json::String(number) => number.parse::<u64>().ok(),
+        _ => None,
+    };
+    let number = match number {
+        Some(number) if number > 0 && number <= u8::MAX as u64 => number as u8,
+        _ => return Err(invalid_field("clues")),
+    };
+
+    let mut parsed = match text.as_str() {
+        Some(text) => CrosswordClue::new(number, text),
+        None => return Err(invalid_field("clues")),
+    };
+    match clue.get("enumeration").map(Json::as_str) {
+        Some(Some(enumeration)) => {
+            let enumeration =
+                Enumeration::parse(enumeration).map_err(|_| invalid_field("enumeration"))?;
+            parsed = parsed.with_enumeration(enumeration);
+        }
+        Some(None) => return Err(invalid_field("enumeration")),
+        None => {}
+    }
+
+    Ok(Some(parsed))
+}
+
+fn parse_word_search(json: &Json, width: u8, height: u8) -> Result<WordSearchData, Error> {
+    let puzzle = grid(json, "puzzle", width, height)?.ok_or_else(|| missing_field("puzzle"))?;
+
+    let mut letters = Vec::new();
+    for row in puzzle {
+        let mut cells = Vec::new();
+        for cell in row {
+            let letter = cell
+                .as_str()
+                .or_else(|| cell.get("cell").and_then(Json::as_str));
+            let mut chars = letter.unwrap_or_default().chars();
+            match (chars.next(), chars.next()) {
+                (Some(letter), None) => cells.push(letter.to_ascii_uppercase()),
+                _ => return Err(invalid_field("puzzle")),
+            }
+        }
+        letters.push(cells);
+    }
+
+    let words: Vec<String> = match json.get("solution") {
+        Some(Json::Array(words)) => words
+            .iter()
+            .map(|word| word.as_str().map(str::to_string))
+            .collect::<Option<Vec<String>>>()
+            .ok_or_else(|| invalid_field("solution"))?,
+        Some(Json::Object(words)) => words.iter().map(|(word, _)| word.clone()).collect(),
+        Some(_) => return Err(invalid_field("solution")),
+        None => Vec::new(),
+    };
+    let words = words.iter().map(|word| word.to_ascii_uppercase()).collect();
+
+    WordSearchData::new(width, height, words, letters)
+}
+
+fn write_crossword(
+    crossword: &CrosswordData,
+    preserved: &[(String, Json)],
+    fields: &mut Vec<(String, Json)>,
+) {
+    fields.push((
+        "dimensions".to_string(),
+        dimensions(crossword.width, crossword.height),
+    ));
+
+    let puzzle = crossword
+        .crossword_data
+        .iter()
+        .map(|row| {
+            Json::Array(
+                row.iter()
+                    .map(|cell| match cell.value {
+                        CrosswordBoxValue::Solid => string(DEFAULT_BLOCK),
+                        CrosswordBoxValue::Void => Json::Null,
+                        _ if !cell.style.is_plain() || cell.bars.any() => Json::Object(vec![
+                            ("cell".to_string(), Json::Number(cell.number as f64)),
+                            ("style".to_string(), style(cell)),
+                        ]),
+                        _ => Json::Number(cell.number as f64),
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    fields.push(("puzzle".to_string(), Json::Array(puzzle)));
+
+    let solution = crossword
+        .crossword_data
+        .iter()
+        .map(|row| {
+            Json::Array(
+                row.iter()
+                    .map(|cell| match &cell.value {
+                        CrosswordBoxValue::Solid => string(DEFAULT_BLOCK),
+                        CrosswordBoxValue::Letter(letter) => string(&letter.to_string()),
+                        CrosswordBoxValue::Rebus(rebus) => string(rebus),
+                        CrosswordBoxValue::Empty | CrosswordBoxValue::Void => Json::Null,
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    fields.push(("solution".to_string(), Json::Array(solution)));
+
+    let clue_list = |clues: &[CrosswordClue]| {
+        Json::Array(
+            clues
+                .iter()
+                .map(|clue| match &clue.enumeration {
+                    Some(enumeration) => Json::Object(vec![
+                        ("number".to_string(), Json::Number(clue.number as f64)),
+                        ("clue".to_string(), string(&clue.value)),
+                        ("enumeration".to_string(), string(&enumeration.to_string())),
+                    ]),
+                    None => {
+                        Json::Array(vec![Json::Number(clue.number as f64), string(&clue.value)])
+                    }
+                })
+                .collect(),
+        )
+    };
+
+    let mut clues = vec![
+        ("Across".to_string(), clue_list(&crossword.horizontal_clues)),
+        ("Down".to_string(), clue_list(&crossword.vertical_clues)),
+    ];
+    if let Some((_, Json::Object(extra))) = preserved.iter().find(|(key, _)| key == "clues") {
+        clues.extend(extra.iter().cloned());
+    }
+    fields.push(("clues".to_string(), Json::Object(clues)));
+}
+
+fn style(cell: &CrosswordBox) -> Json {
+    let style = cell.style;
+    let mut fields = Vec::new();
+    if style.circled {
+        fields.push(("shapebg".to_string(), string("circle")));
+    }
+    if let Some(shade) = style.shade {
+        fields.push(("color".to_string(), string(&hex_color(shade)[1..])));
+    }
+    if style.bold_border {
+        fields.push(("border".to_string(), Json::Number(2.0)));
+    }
+
+    let mut barred = String::new();
+    if cell.bars.right {
+        barred.push('R');
+    }
+    if cell.bars.bottom {
+        barred.push('B');
+    }
+    if !barred.is_empty() {
+        fields.push(("barred".to_string(), string(&barred)));
+    }
+
+    Json::Object(fields)
+}
+
+fn write_word_search(word_search: &WordSearchData, fields: &mut Vec<(String, Json)>) {
+    fields.push((
+        "dimensions".to_string(),
+        dimensions(word_search.width, word_search.height),
+    ));
+
+    let puzzle = word_search
+        .grid
+        .iter()
+        .map(|row| {
+            Json::Array(
+                row.iter()
+                    .map(|letter| string(&letter.to_string()))
+                    .collect(),
+            )
+        })
+        .collect();
+    fields.push(("puzzle".to_string(), Json::Array(puzzle)));
+
+    let words = word_search.words.iter().map(|word| string(word)).collect();
+    fields.push(("solution".to_string(), Json::Array(words)));
+}
+
+fn grid(json: &Json, field: &str, width: u8, height: u8) -> Result<Option<Vec<Vec<Json>>>, Error> {
+    let rows = match json.get(field) {
+        Some(Json::Array(rows)) => rows,
+        Some(_) => return Err(invalid_field(field)),
+        None => return Ok(None),
+    };
+
+    if rows.len() != height as usize {
+        return Err(invalid_field(field));
+    }
+
+    let mut grid = Vec::new();
+    for row in rows {
+        match row.as_array() {
+            Some(cells) if cells.len() == width as usize => grid.push(cells.clone()),
+            _ => return Err(invalid_field(field)),
+        }
+    }
+
+    Ok(Some(grid))
+}
+
+fn dimension(dimensions: &Json, field: &str) -> Result<u8, Error> {
+    match dimensions.get(field).and_then(Json::as_u64) {
+        Some(value) if value > 0 && value <= u8::MAX as u64 => Ok(value as u8),
+        Some(_) => Err(invalid_field("dimensions")),
+        None => Err(missing_field("dimensions")),
+    }
+}
+
+fn dimensions(width: u8, height: u8) -> Json {
+    Json::Object(vec![
+        ("width".to_string(), Json::Number(width as f64)),
+        ("height".to_string(), Json::Number(height as f64)),
+    ])
+}
+
+fn string(value: &str) -> Json {
+    Json::String(value.to_string())
+}
+
+fn missing_field(field: &str) -> Error {
+    Error::IpuzMissingField {
+        field: field.to_string(),
+    }
+}
+
+fn invalid_field(field: &str) -> Error {
+    Error::IpuzInvalidField {
+        field: field.to_string(),
+    }
+}
@@ -0,0 +1,511 @@
+//! JPZ (Crossword Compiler XML) import and export for rectangular crosswords.
+//!
+//! Cells use 1-based `x`/`y` coordinates. Every clue points at a word, which lists its
+//! cells as ranges like `x="1-5" y="3"`. The direction of a clue is taken from the shape
+//! of its word, since the titles of the clue lists are free text.
+//!
+//! Circles and shading map to the `background-shape` and `background-color` of a cell.
+//! JPZ has no bold borders, so those are left out on export with a warning. Bars on any
+//! edge are read, but only right and bottom bars are written. Diagramless crosswords are
+//! exported as regular ones, also with a warning. Enumerations are kept in the `format`
+//! attribute of a clue.
+
+use crate::crossword::{
+    validate_rebus, Bars, CellStyle, CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData,
+    Enumeration,
+};
+use crate::entry::Direction;
+use crate::utils::{hex_color, parse_hex_color};
+use crate::xml::Element;
+use crate::{current_timestamp, imported_metadata, Error, GameData, TggFile, Warning};
+
+const APPLET_NAMESPACE: &str = "http://crossword.info/xml/crossword-compiler-applet";
+const PUZZLE_NAMESPACE: &str = "http://crossword.info/xml/rectangular-puzzle";
+
+impl TggFile {
+    pub fn from_jpz(text: &str) -> Result<(TggFile, Vec<Warning>), Error> {
+        parse(text)
+    }
+
+    pub fn to_jpz(&self) -> Result<(String, Vec<Warning>), Error> {
+        match &self.gamedata {
+            GameData::Crossword(crossword) => write(self, crossword),
+            _ => Err(Error::NotACrossword),
+        }
+    }
+}
+
+fn parse(text: &str) -> Result<(TggFile, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+    let root = Element::parse(text)?;
+
+    let puzzle = match root.find("rectangular-puzzle") {
+        Some(puzzle) => puzzle,
+        None => return Err(missing_element("rectangular-puzzle")),
+    };
+
+    let crossword = match puzzle.child("crossword") {
+        Some(crossword) => crossword,
+        None => return Err(Error::UnsupportedJpzPuzzle),
+    };
+
+    let grid = match crossword.child("grid") {
+        Some(grid) => grid,
+        None => return Err(missing_element("grid")),
+    };
+
+    let width = dimension(grid, "width")?;
+    let height = dimension(grid, "height")?;
+
+    let mut crossword_data: Vec<Vec<CrosswordBox>> = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| CrosswordBox {
+                    number: 0,
+                    value: CrosswordBoxValue::Empty,
+                    style: CellStyle::default(),
+                    bars: Bars::default(),
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut neighbour_bars = Vec::new();
+    for cell in grid.children_named("cell") {
+        let (row, column) = cell_position(cell, width, height)?;
+        let position = (row as u8, column as u8);
+
+        let number = match cell.attribute("number") {
+            Some(number) => match number.parse::<u8>() {
+                Ok(number) => number,
+                Err(_) => return Err(invalid_attribute("number")),
+            },
+            None => 0,
+        };
+
+        let value = match cell.attribute("type") {
+            Some("block") => CrosswordBoxValue::Solid,
+            Some("void") => CrosswordBoxValue::Void,
+            _ => parse_solution(cell.attribute("solution"), position, &mut warnings),
+        };
+
+        let style = CellStyle {
+            circled: cell.attribute("background-shape") == Some("circle"),
+            shade: cell.attribute("background-color").and_then(parse_hex_color),
+            bold_border: false,
+        };
+
+        let bar = |name: &str| cell.attribute(name) == Some("true");
+        let bars = Bars {
+            right: bar("right-bar"),
+            bottom: bar("bottom-bar"),
+        };
+        // Left and top bars belong to the neighbouring box
+        if bar("left-bar") && column > 0 {
+            neighbour_bars.push((row, column - 1, Direction::Across));
+        }
+        if bar("top-bar") && row > 0 {
+            neighbour_bars.push((row - 1, column, Direction::Down));
+        }
+
+        crossword_data[row][column] = CrosswordBox::new(number, value)?
+            .with_style(style)
+            .with_bars(bars);
+    }
+
+    for (row, column, direction) in neighbour_bars {
+        let bars = &mut crossword_data[row][column].bars;
+        match direction {
+            Direction::Across => bars.right = true,
+            Direction::Down => bars.bottom = true,
+        }
+    }
+
+    // Words map their id to the direction and start of the entry
+    let mut words: Vec<(String, Direction, usize, usize)> = Vec::new();
+    for word in crossword.children_named("word") {
+        let id = match word.attribute("id") {
+            Some(id) => id.to_string(),
+            None => return Err(missing_attribute("id")),
+        };
+
+        match word_shape(word, width, height)? {
+            Some((direction, row, column)) => words.push((id, direction, row, column)),
+            None => warnings.push(Warning::JpzWordIgnored { id }),
+        }
+    }
+
+    let mut horizontal_clues = Vec::new();
+    let mut vertical_clues = Vec::new();
+
+    for clues in crossword.children_named("clues") {
+        for clue in clues.children_named("clue") {
+            let word_id = match clue.attribute("word") {
+                Some(word_id) => word_id,
+                None => return Err(missing_attribute("word")),
+            };
+
+            let (direction, row, column) = match words.iter().find(|(id, ..)| id == word_id) {
+                Some((_, direction, row, column)) => (*direction, *row, *column),
+                None => {
+                    warnings.push(Warning::JpzClueIgnored {
+                        word: word_id.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let number = match clue.attribute("number") {
+                Some(number) => match number.parse::<u8>() {
+                    Ok(number) => number,
+                    Err(_) => return Err(invalid_attribute("number")),
+                },
+                None => crossword_data
+                    .get(row)
+                    .and_then(|cells| cells.get(column))
+                    .map_or(0, |cell| cell.number),
+            };
+
+            let mut parsed = CrosswordClue::new(number, clue.text().trim());
+            if let Some(format) = clue.attribute("format") {
+                let enumeration =
+                    Enumeration::parse(format).map_err(|_| invalid_attribute("format"))?;
+                parsed = parsed.with_enumeration(enumeration);
+            }
+            match direction {
+                Direction::Across => horizontal_clues.push(parsed),
+                Direction::Down => vertical_clues.push(parsed),
+            }
+        }
+    }
+
+    if horizontal_clues.len() + vertical_clues.len() > u8::MAX as usize {
+        return Err(Error::TooManyEntries);
+    }
+
+    let crossword = CrosswordData::new(
+        width,
+        height,
+        horizontal_clues,
+        vertical_clues,
+        crossword_data,
+    )?;
+
+    let metadata_text = |name: &str| {
+        puzzle
+            .child("metadata")
+            .and_then(|metadata| metadata.child(name))
+            .map(|element| element.text().trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let description = match metadata_text("description") {
+        description if description.is_empty() => metadata_text("copyright"),
+        description => description,
+    };
+    let title = imported_metadata(metadata_text("title"), "title", &mut warnings);
+    let description = imported_metadata(description, "description", &mut warnings);
+    let author = imported_metadata(metadata_text("creator"), "author", &mut warnings);
+
+    let tgg_file = TggFile::assemble(
+        &title,
+        &description,
+        &author,
+        current_timestamp(),
+        GameData::Crossword(crossword),
+        Vec::new(),
+    );
+
+    Ok((tgg_file, warnings))
+}
+
+fn parse_solution(
+    solution: Option<&str>,
+    (row, column): (u8, u8),
+    warnings: &mut Vec<Warning>,
+) -> CrosswordBoxValue {
+    let solution = match solution {
+        Some(solution) => solution,
+        None => return CrosswordBoxValue::Empty,
+    };
+
+    let mut chars = solution.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => CrosswordBoxValue::Empty,
+        (Some(letter), None) if letter.is_ascii_alphabetic() => {
+            CrosswordBoxValue::Letter(letter.to_ascii_uppercase())
+        }
+        (Some(_), Some(_)) => {
+            let rebus = solution.to_ascii_uppercase();
+            if validate_rebus(&rebus).is_ok() {
+                CrosswordBoxValue::Rebus(rebus)
+            } else {
+                warnings.push(Warning::JpzRebusIgnored { row, column });
+                CrosswordBoxValue::Empty
+            }
+        }
+        (Some(found), None) => {
+            warnings.push(Warning::UnsupportedCharacter { row, column, found });
+            CrosswordBoxValue::Empty
+        }
+    }
+}
+
+/// Returns the direction and starting row and column of a straight word, or `None` for
+/// words that aren't a single horizontal or vertical run.
+fn word_shape(
+    word: &Element,
+    width: u8,
+    height: u8,
+) -> Result<Option<(Direction, usize, usize)>, Error> {
+    let mut cells: Vec<(usize, usize)> = Vec::new();
+
+    if let (Some(x), Some(y)) = (word.attribute("x"), word.attribute("y")) {
+        cells.extend(range_cells(x, y, width, height)?);
+    }
+
+    for part in word.children_named("cells") {
+        match (part.attribute("x"), part.attribute("y")) {
+            (Some(x), Some(y)) => cells.extend(range_cells(x, y, width, height)?),
+            _ => return Err(missing_attribute("x")),
+        }
+    }
+
+    if cells.len() < 2 {
+        return Ok(None);
+    }
+
+    let (row, column) = cells[0];
+    let across = cells
+        .iter()
+        .enumerate()
+        .all(|(index, &cell)| cell == (row, column + index));
+    let down = cells
+        .iter()
+        .enumerate()
+        .all(|(index, &cell)| cell == (row + index, column));
+
+    Ok(match (across, down) {
+        (true, _) => Some((Direction::Across, row, column)),
+        (_, true) => Some((Direction::Down, row, column)),
+        _ => None,
+    })
+}
+
+/// Expands `x="1-3" y="2"` style ranges into 0-based `(row, column)` pairs.
+fn range_cells(x: &str, y: &str, width: u8, height: u8) -> Result<Vec<(usize, usize)>, Error> {
+    let columns = parse_range(x, "x", width)?;
+    let rows = parse_range(y, "y", height)?;
+
+    let mut cells = Vec::new();
+    for row in &rows {
+        for column in &columns {
+            cells.push((*row, *column));
+        }
+    }
+
+    Ok(cells)
+}
+
+/// Expands a range, checking both ends against the grid before any boxes are listed.
+fn parse_range(range: &str, name: &str, limit: u8) -> Result<Vec<usize>, Error> {
+    let parse = |value: &str| match value.trim().parse::<usize>() {
+        Ok(value) if value > 0 && value <= limit as usize => Ok(value - 1),
+        _ => Err(invalid_attribute(name)),
+    };
+
+    match range.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if start <= end {
+                Ok((start..=end).collect())
+            } else {
+                Ok((end..=start).rev().collect())
+            }
+        }
+        None => Ok(vec![parse(range)?]),
+    }
+}
+
+fn cell_position(cell: &Element, width: u8, height: u8) -> Result<(usize, usize), Error> {
+    let coordinate = |name: &str, limit: u8| match cell.attribute(name) {
+        Some(value) => match value.parse::<usize>() {
+            Ok(value) if value > 0 && value <= limit as usize => Ok(value - 1),
+            _ => Err(invalid_attribute(name)),
+        },
+        None => Err(missing_attribute(name)),
+    };
+
+    Ok((coordinate("y", height)?, coordinate("x", width)?))
+}
+
+fn dimension(grid: &Element, name: &str) -> Result<u8, Error> {
+    match grid.attribute(name) {
+        Some(value) => match value.parse::<u8>() {
+            Ok(value) if value > 0 => Ok(value),
+            _ => Err(invalid_attribute(name)),
+        },
+        None => Err(missing_attribute(name)),
+    }
+}
+
+fn write(tgg_file: &TggFile, crossword: &CrosswordData) -> Result<(String, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+    if crossword.diagramless {
+        warnings.push(Warning::JpzDiagramlessIgnored);
+    }
+
+    let mut grid = Element::new("grid")
+        .with_attribute("width", &crossword.width.to_string())
+        .with_attribute("height", &crossword.height.to_string())
+        .with_child(Element::new("grid-look").with_attribute("numbering-scheme", "normal"));
+
+    for (row, cells) in crossword.crossword_data.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            let mut element = Element::new("cell")
+                .with_attribute("x", &(column + 1).to_string())
+                .with_attribute("y", &(row + 1).to_string());
+
+            match &cell.value {
+                CrosswordBoxValue::Solid => element = element.with_attribute("type", "block"),
+                CrosswordBoxValue::Void => element = element.with_attribute("type", "void"),
+                CrosswordBoxValue::Letter(letter) => {
+                    element = element.with_attribute("solution", &letter.to_string())
+                }
+                CrosswordBoxValue::Rebus(rebus) => {
+                    element = element.with_attribute("solution", rebus)
+                }
+                CrosswordBoxValue::Empty => {}
+            }
+
+            if cell.number != 0 {
+                element = element.with_attribute("number", &cell.number.to_string());
+            }
+            if cell.style.circled {
+                element = element.with_attribute("background-shape", "circle");
+            }
+            if let Some(shade) = cell.style.shade {
+                element = element.with_attribute("background-color", &hex_color(shade));
+            }
+            if cell.style.bold_border {
+                warnings.push(Warning::JpzStyleIgnored {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+            if cell.bars.right {
+                element = element.with_attribute("right-bar", "true");
+            }
+            if cell.bars.bottom {
+                element = element.with_attribute("bottom-bar", "true");
+            }
+
+            grid = grid.with_child(element);
+        }
+    }
+
+    let mut crossword_element = Element::new("crossword").with_child(grid);
+    let mut word_id = 0;
+    let mut clue_lists = Vec::new();
+    let entries = crossword.entries();
+
+    for (title, clues, direction) in [
+        ("Across", &crossword.horizontal_clues, Direction::Across),
+        ("Down", &crossword.vertical_clues, Direction::Down),
+    ] {
+        let mut list = Element::new("clues")
+            .with_attribute("ordering", "normal")
+            .with_child(Element::new("title").with_child(Element::new("b").with_text(title)));
+
+        for clue in clues {
+            let entry = match entries
+                .iter()
+                .find(|entry| entry.number == clue.number && entry.direction == direction)
+            {
+                Some(entry) => entry,
+                // JPZ clues point at a word, so a clue without one can't be written
+                None if direction == Direction::Across => {
+                    return Err(Error::HorizontalClueContainsInvalidNumber {
+                        number: clue.number,
+                    })
+                }
+                None => {
+                    return Err(Error::VerticalClueContainsInvalidNumber {
+                        number: clue.number,
+                    })
+                }
+            };
+            let start = (entry.start.0 as usize, entry.start.1 as usize);
+            let length = entry.length as usize;
+            word_id += 1;
+
+            let (x, y) = match direction {
+                Direction::Across => (
+                    format!("{}-{}", start.1 + 1, start.1 + length),
+                    (start.0 + 1).to_string(),
+                ),
+                Direction::Down => (
+                    (start.1 + 1).to_string(),
+                    format!("{}-{}", start.0 + 1, start.0 + length),
+                ),
+            };
+
+            crossword_element = crossword_element.with_child(
+                Element::new("word")
+                    .with_attribute("id", &word_id.to_string())
+                    .with_attribute("x", &x)
+                    .with_attribute("y", &y),
+            );
+
+            let mut element = Element::new("clue")
+                .with_attribute("word", &word_id.to_string())
+                .with_attribute("number", &clue.number.to_string());
+            if let Some(enumeration) = &clue.enumeration {
+                element = element.with_attribute("format", &enumeration.to_string());
+            }
+            list = list.with_child(element.with_text(&clue.value));
+        }
+
+        clue_lists.push(list);
+    }
+
+    for list in clue_lists {
+        crossword_element = crossword_element.with_child(list);
+    }
+
+    let metadata = Element::new("metadata")
+        .with_child(Element::new("title").with_text(&tgg_file.metadata.title))
+        .with_child(Element::new("creator").with_text(&tgg_file.metadata.author))
+        .with_child(Element::new("description").with_text(&tgg_file.metadata.description));
+
+    let document = Element::new("crossword-compiler-applet")
+        .with_attribute("xmlns", APPLET_NAMESPACE)
+        .with_child(
+            Element::new("rectangular-puzzle")
+                .with_attribute("xmlns", PUZZLE_NAMESPACE)
+                .with_attribute("alphabet", "ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+                .with_child(metadata)
+                .with_child(crossword_element),
+        )
+        .to_document();
+
+    Ok((document, warnings))
+}
+
+fn missing_element(name: &str) -> Error {
+    Error::JpzMissingElement {
+        name: name.to_string(),
+    }
+}
+
+fn missing_attribute(name: &str) -> Error {
+    Error::JpzMissingAttribute {
+        name: name.to_string(),
+    }
+}
+
+fn invalid_attribute(name: &str) -> Error {
+    Error::JpzInvalidAttribute {
+        name: name.to_string(),
+    }
+}
@@ -0,0 +1,380 @@
+//! Minimal JSON reader and writer, just enough for the ipuz format.
+
+use crate::Error;
+
+/// Arrays and objects nested deeper than this are rejected instead of overflowing the
+/// stack. ipuz files never need more than a few levels.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys are kept in their original order so files round trip unchanged.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn parse(text: &str) -> Result<Json, Error> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            offset: 0,
+            depth: 0,
+        };
+
+        parser.skip_whitespace();
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+
+        if parser.offset != parser.chars.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+
+        Ok(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(value) if *value >= 0.0 && value.fract() == 0.0 => Some(*value as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Vec<(String, Json)>> {
+        match self {
+            Json::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Serializes the value, indenting nested arrays and objects except for arrays
+    /// that only contain scalars, which keeps grids on one line per row.
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.write(&mut output, 0);
+        output
+    }
+
+    fn write(&self, output: &mut String, indent: usize) {
+        match self {
+            Json::Null => output.push_str("null"),
+            Json::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+            Json::Number(value) => output.push_str(&format_number(*value)),
+            Json::String(value) => write_string(output, value),
+            Json::Array(values) => {
+                let scalars = values
+                    .iter()
+                    .all(|value| !matches!(value, Json::Array(_) | Json::Object(_)));
+                if values.is_empty() || scalars {
+                    output.push('[');
+                    for (index, value) in values.iter().enumerate() {
+                        if index > 0 {
+                            output.push_str(", ");
+                        }
+                        value.write(output, indent);
+                    }
+                    output.push(']');
+                    return;
+                }
+
+                output.push_str("[\n");
+                for (index, value) in values.iter().enumerate() {
+                    push_indent(output, indent + 1);
+                    value.write(output, indent + 1);
+                    if index + 1 < values.len() {
+                        output.push(',');
+                    }
+                    output.push('\n');
+                }
+                push_indent(output, indent);
+                output.push(']');
+            }
+            Json::Object(fields) => {
+                if fields.is_empty() {
+                    output.push_str("{}");
+                    return;
+                }
+
+                output.push_str("{\n");
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    push_indent(output, indent + 1);
+                    write_string(output, key);
+                    output.push_str(": ");
+                    value.write(output, indent + 1);
+                    if index + 1 < fields.len() {
+                        output.push(',');
+                    }
+                    output.push('\n');
+                }
+                push_indent(output, indent);
+                output.push('}');
+            }
+        }
+    }
+}
+
+fn push_indent(output: &mut String, indent: usize) {
+    for _ in 0..indent {
+        output.push_str("  ");
+    }
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+struct Parser {
+    chars: Vec<char>,
+    offset: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn error(&self, reason: &str) -> Error {
+        Error::JsonSyntax {
+            offset: self.offset as u32,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected)));
+        }
+        self.offset += 1;
+        Ok(())
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, Error> {
+        for expected in literal.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Json, Error> {
+        match self.peek() {
+            Some('n') => self.parse_literal("null", Json::Null),
+            Some('t') => self.parse_literal("true", Json::Bool(true)),
+            Some('f') => self.parse_literal("false", Json::Bool(false)),
+            Some('"') => Ok(Json::String(self.parse_string()?)),
+            Some('[') => self.parse_nested(Parser::parse_array),
+            Some('{') => self.parse_nested(Parser::parse_object),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Parser) -> Result<Json, Error>,
+    ) -> Result<Json, Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_number(&mut self) -> Result<Json, Error> {
+        let start = self.offset;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.offset += 1;
+        }
+
+        let text: String = self.chars[start..self.offset].iter().collect();
+        match text.parse::<f64>() {
+            Ok(value) => Ok(Json::Number(value)),
+            Err(_) => {
+                self.offset = start;
+                Err(self.error("invalid number"))
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut value = String::new();
+
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return Err(self.error("unterminated string")),
+            };
+            self.offset += 1;
+
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some(escaped) => escaped,
+                        None => return Err(self.error("unterminated string")),
+                    };
+                    self.offset += 1;
+
+                    match escaped {
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        '/' => value.push('/'),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'u' => {
+                            let high = self.parse_hex()?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                self.expect('\\')?;
+                                self.expect('u')?;
+                                let low = self.parse_hex()?;
+                                0x10000
+                                    + ((high - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                high
+                            };
+                            value.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, Error> {
+        if self.offset + 4 > self.chars.len() {
+            return Err(self.error("unterminated unicode escape"));
+        }
+
+        let digits: String = self.chars[self.offset..self.offset + 4].iter().collect();
+        match u32::from_str_radix(&digits, 16) {
+            Ok(code) => {
+                self.offset += 4;
+                Ok(code)
+            }
+            Err(_) => Err(self.error("invalid unicode escape")),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, Error> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.offset += 1;
+            return Ok(Json::Array(values));
+        }
+
+        loop {
+            self.skip_whitespace();
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.offset += 1,
+                Some(']') => {
+                    self.offset += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, Error> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.offset += 1;
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.offset += 1,
+                Some('}') => {
+                    self.offset += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+}
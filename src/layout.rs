@@ -0,0 +1,388 @@
+//! Builds a crossword from answer and clue pairs, for "make your own crossword" puzzles.
+//!
+//! Words are placed one at a time, longest first, each crossing the words already in the
+//! grid. Newly placed letters may not touch other letters side by side, so the only
+//! entries in the result are the given answers. Several randomized attempts are made and
+//! the layout that places the most words in the smallest area is kept.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordClue, CrosswordData};
+use crate::entry::Direction;
+use crate::utils::Rng;
+use crate::word_list::normalize;
+use crate::Error;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct LayoutOptions {
+    pub max_width: u8,
+    pub max_height: u8,
+    /// The same seed always gives the same layout.
+    pub seed: u64,
+    pub attempts: u32,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> LayoutOptions {
+        LayoutOptions {
+            max_width: 15,
+            max_height: 15,
+            seed: 0,
+            attempts: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub crossword: CrosswordData,
+    /// Answers that didn't fit, as they were given.
+    pub unplaced: Vec<String>,
+}
+
+impl CrosswordData {
+    /// Lays out `(answer, clue)` pairs in a grid. Spaces and punctuation are dropped from
+    /// answers, answers with other characters can't be placed.
+    pub fn generate_layout(
+        pairs: &[(&str, &str)],
+        options: &LayoutOptions,
+    ) -> Result<Layout, Error> {
+        let words: Vec<Word> = pairs
+            .iter()
+            .enumerate()
+            .map(|(index, (answer, clue))| Word {
+                index,
+                letters: normalize(answer).map(String::into_bytes),
+                clue: clue.to_string(),
+            })
+            .collect();
+
+        let mut rng = Rng::new(options.seed);
+        let mut best: Option<Attempt> = None;
+
+        for _ in 0..options.attempts.max(1) {
+            let attempt = attempt(&words, options, &mut rng);
+            if best
+                .as_ref()
+                .map_or(true, |best| attempt.is_better_than(best))
+            {
+                best = Some(attempt);
+            }
+        }
+
+        let best = match best {
+            Some(best) if !best.placements.is_empty() => best,
+            _ => return Err(Error::NoWordsPlaced),
+        };
+
+        let crossword = best.to_crossword(&words)?;
+        let unplaced = pairs
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !best.placements.iter().any(|p| p.word == *index))
+            .map(|(_, (answer, _))| answer.to_string())
+            .collect();
+
+        Ok(Layout {
+            crossword,
+            unplaced,
+        })
+    }
+}
+
+struct Word {
+    index: usize,
+    /// `None` when the answer can't go in a grid.
+    letters: Option<Vec<u8>>,
+    clue: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    word: usize,
+    row: i32,
+    column: i32,
+    direction: Direction,
+}
+
+#[derive(Default)]
+struct Cell {
+    letter: u8,
+    across: bool,
+    down: bool,
+}
+
+#[derive(Default)]
+struct Attempt {
+    cells: HashMap<(i32, i32), Cell>,
+    placements: Vec<Placement>,
+    crossings: usize,
+    bounds: Option<(i32, i32, i32, i32)>,
+}
+
+impl Attempt {
+    fn area(&self) -> i32 {
+        match self.bounds {
+            Some((top, left, bottom, right)) => (bottom - top + 1) * (right - left + 1),
+            None => 0,
+        }
+    }
+
+    fn is_better_than(&self, other: &Attempt) -> bool {
+        (self.placements.len(), self.crossings, -self.area())
+            > (other.placements.len(), other.crossings, -other.area())
+    }
+
+    /// Number of existing letters the word would cross, or `None` if it doesn't fit.
+    fn check(&self, letters: &[u8], row: i32, column: i32, direction: Direction) -> Option<usize> {
+        let (row_step, column_step) = steps(direction);
+        let occupied = |row: i32, column: i32| self.cells.contains_key(&(row, column));
+
+        // Nothing directly before or after the word
+        if occupied(row - row_step, column - column_step)
+            || occupied(
+                row + row_step * letters.len() as i32,
+                column + column_step * letters.len() as i32,
+            )
+        {
+            return None;
+        }
+
+        let mut crossings = 0;
+        for (offset, letter) in letters.iter().enumerate() {
+            let cell_row = row + row_step * offset as i32;
+            let cell_column = column + column_step * offset as i32;
+
+            match self.cells.get(&(cell_row, cell_column)) {
+                Some(cell) => {
+                    let taken = match direction {
+                        Direction::Across => cell.across,
+                        Direction::Down => cell.down,
+                    };
+                    if cell.letter != *letter || taken {
+                        return None;
+                    }
+                    crossings += 1;
+                }
+                None => {
+                    // New letters can't sit next to letters of parallel words
+                    if occupied(cell_row + column_step, cell_column + row_step)
+                        || occupied(cell_row - column_step, cell_column - row_step)
+                    {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        Some(crossings)
+    }
+
+    fn bounds_with(
+        &self,
+        length: usize,
+        row: i32,
+        column: i32,
+        direction: Direction,
+    ) -> (i32, i32, i32, i32) {
+        let (row_step, column_step) = steps(direction);
+        let end_row = row + row_step * (length as i32 - 1);
+        let end_column = column + column_step * (length as i32 - 1);
+
+        match self.bounds {
+            Some((top, left, bottom, right)) => (
+                top.min(row),
+                left.min(column),
+                bottom.max(end_row),
+                right.max(end_column),
+            ),
+            None => (row, column, end_row, end_column),
+        }
+    }
+
+    fn place(&mut self, word: usize, letters: &[u8], row: i32, column: i32, direction: Direction) {
+        let (row_step, column_step) = steps(direction);
+        self.bounds = Some(self.bounds_with(letters.len(), row, column, direction));
+
+        for (offset, letter) in letters.iter().enumerate() {
+            let cell = self
+                .cells
+                .entry((
+                    row + row_step * offset as i32,
+                    column + column_step * offset as i32,
+                ))
+                .or_default();
+            if cell.letter != 0 {
+                self.crossings += 1;
+            }
+            cell.letter = *letter;
+            match direction {
+                Direction::Across => cell.across = true,
+                Direction::Down => cell.down = true,
+            }
+        }
+
+        self.placements.push(Placement {
+            word,
+            row,
+            column,
+            direction,
+        });
+    }
+
+    fn to_crossword(&self, words: &[Word]) -> Result<CrosswordData, Error> {
+        let (top, left, bottom, right) = self.bounds.unwrap_or_default();
+        let width = (right - left + 1) as u8;
+        let height = (bottom - top + 1) as u8;
+
+        let grid: Vec<Vec<CrosswordBoxValue>> = (top..=bottom)
+            .map(|row| {
+                (left..=right)
+                    .map(|column| match self.cells.get(&(row, column)) {
+                        Some(cell) => CrosswordBoxValue::Letter(cell.letter as char),
+                        None => CrosswordBoxValue::Solid,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let numbered = CrosswordData::from_grid(width, height, vec![], vec![], grid)?;
+
+        let mut horizontal_clues = Vec::new();
+        let mut vertical_clues = Vec::new();
+        for placement in &self.placements {
+            let row = (placement.row - top) as usize;
+            let column = (placement.column - left) as usize;
+            let clue = CrosswordClue::new(
+                numbered.crossword_data[row][column].number,
+                &words[placement.word].clue,
+            );
+
+            match placement.direction {
+                Direction::Across => horizontal_clues.push(clue),
+                Direction::Down => vertical_clues.push(clue),
+            }
+        }
+        horizontal_clues.sort_by_key(|clue| clue.number);
+        vertical_clues.sort_by_key(|clue| clue.number);
+
+        CrosswordData::new(
+            width,
+            height,
+            horizontal_clues,
+            vertical_clues,
+            numbered.crossword_data,
+        )
+    }
+}
+
+fn attempt(words: &[Word], options: &LayoutOptions, rng: &mut Rng) -> Attempt {
+    let max_width = options.max_width as i32;
+    let max_height = options.max_height as i32;
+
+    // Longest first, words of the same length in random order
+    let mut order: Vec<&Word> = words
+        .iter()
+        .filter(|word| {
+            word.letters
+                .as_ref()
+                .is_some_and(|letters| letters.len() > 1)
+        })
+        .collect();
+    rng.shuffle(&mut order);
+    order.sort_by_key(|word| Reverse(word.letters.as_ref().map_or(0, Vec::len)));
+
+    let mut layout = Attempt::default();
+    let mut pending = order;
+
+    // Words that don't cross anything yet get another chance once more words are in
+    loop {
+        let mut deferred = Vec::new();
+
+        for word in &pending {
+            let letters = word.letters.as_deref().unwrap_or_default();
+
+            if layout.placements.is_empty() {
+                let direction = if letters.len() as i32 <= max_width {
+                    Direction::Across
+                } else {
+                    Direction::Down
+                };
+                if letters.len() as i32 <= max_width.max(max_height) {
+                    layout.place(word.index, letters, 0, 0, direction);
+                } else {
+                    deferred.push(*word);
+                }
+                continue;
+            }
+
+            let mut candidates = Vec::new();
+            for (&(row, column), cell) in &layout.cells {
+                for (offset, letter) in letters.iter().enumerate() {
+                    if *letter != cell.letter {
+                        continue;
+                    }
+
+                    for direction in [Direction::Across, Direction::Down] {
+                        let (row_step, column_step) = steps(direction);
+                        let start_row = row - row_step * offset as i32;
+                        let start_column = column - column_step * offset as i32;
+
+                        let (top, left, bottom, right) =
+                            layout.bounds_with(letters.len(), start_row, start_column, direction);
+                        if bottom - top + 1 > max_height || right - left + 1 > max_width {
+                            continue;
+                        }
+
+                        if let Some(crossings) =
+                            layout.check(letters, start_row, start_column, direction)
+                        {
+                            let area = (bottom - top + 1) * (right - left + 1);
+                            candidates.push((crossings, area, start_row, start_column, direction));
+                        }
+                    }
+                }
+            }
+
+            // Most crossings, then the smallest grid, ties broken randomly
+            candidates.sort_by_key(|&(crossings, area, row, column, direction)| {
+                (
+                    Reverse(crossings),
+                    area,
+                    row,
+                    column,
+                    direction == Direction::Down,
+                )
+            });
+            candidates.dedup();
+            let best = candidates
+                .first()
+                .map(|&(crossings, area, ..)| (crossings, area));
+            let ties: Vec<_> = candidates
+                .iter()
+                .filter(|&&(crossings, area, ..)| Some((crossings, area)) == best)
+                .collect();
+
+            if ties.is_empty() {
+                deferred.push(*word);
+            } else {
+                let &(_, _, row, column, direction) = ties[rng.below(ties.len())];
+                layout.place(word.index, letters, row, column, direction);
+            }
+        }
+
+        if deferred.is_empty() || deferred.len() == pending.len() {
+            break;
+        }
+        pending = deferred;
+    }
+
+    layout
+}
+
+fn steps(direction: Direction) -> (i32, i32) {
+    match direction {
+        Direction::Across => (0, 1),
+        Direction::Down => (1, 0),
+    }
+}
@@ -1,35 +1,84 @@
+pub mod autofill;
+mod check;
 pub mod crossword;
 mod date;
+pub mod entry;
+mod html;
+mod ipuz;
+mod jpz;
+mod json;
+pub mod layout;
+pub mod lint;
 mod load;
+pub mod pdf;
+pub mod player;
+mod puz;
+pub mod render;
+pub mod solver;
+pub mod svg;
+mod text;
 mod utils;
+pub mod word_list;
+pub mod word_search;
+pub mod word_search_generator;
+pub mod word_search_solver;
+mod xml;
 use crate::crossword::{CrosswordBox, CrosswordClue, CrosswordData};
 use crate::date::format_timestamp;
 use crate::load::load;
 use crate::utils::calculate_checksum;
+use crate::word_search::WordSearchData;
 pub mod error;
 pub use crate::error::Error;
+pub mod warning;
+pub use crate::warning::Warning;
+use std::fmt;
+use std::fs;
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const ID: &str = "TalonGamesGame";
 
+/// Latest version of the game data block.
+///
+/// Version 1 files store the game data directly. Later versions start the block with a
+/// zero byte, which can't start version 1 game data since the width is never zero,
+/// followed by the version number.
+///
+/// - 2: extension area before the game data
+/// - 3: rebus boxes
+/// - 4: box styles
+/// - 5: bars
+/// - 6: void boxes
+/// - 7: puzzle flags, for diagramless crosswords
+/// - 8: clue enumerations
+///
+/// Files are written in the lowest version that can hold their data.
+const FORMAT_VERSION: u8 = 8;
+
 #[derive(Debug)]
 pub struct TggFile {
     header: Header,
     metadata: Metadata,
     gamedata: GameData,
+    extensions: Vec<Extension>,
     footer: Footer,
 }
 
 impl TggFile {
     pub fn from_bytes(bytes: Vec<u8>) -> Result<TggFile, Error> {
-        let file = match load(bytes) {
-            Ok(file) => file,
-            Err(err) => return Err(err),
-        };
+        let file = load(bytes)?;
 
         Ok(file)
     }
 
+    pub fn load(path: &Path) -> Result<TggFile, Error> {
+        let bytes = fs::read(path).map_err(Error::Io)?;
+
+        TggFile::from_bytes(bytes)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn custom_crossword(
         title: &str,
         description: &str,
@@ -48,30 +97,90 @@ impl TggFile {
             crossword_data,
         )?;
 
-        let gamedata_checksum = calculate_checksum(crossword.to_bytes());
+        Ok(TggFile::assemble(
+            title,
+            description,
+            author,
+            current_timestamp(),
+            GameData::Crossword(crossword),
+            Vec::new(),
+        ))
+    }
 
-        let metadata = Metadata::create(
+    pub fn custom_word_search(
+        title: &str,
+        description: &str,
+        author: &str,
+        width: u8,
+        height: u8,
+        words: Vec<String>,
+        grid: Vec<Vec<char>>,
+    ) -> Result<TggFile, Error> {
+        let word_search = WordSearchData::new(width, height, words, grid)?;
+
+        Ok(TggFile::assemble(
             title,
             description,
             author,
+            current_timestamp(),
+            GameData::WordSearch(word_search),
+            Vec::new(),
+        ))
+    }
+
+    /// Marks a crossword as diagramless, or as a regular crossword again.
+    pub fn with_diagramless(self, diagramless: bool) -> Result<TggFile, Error> {
+        let mut crossword = match self.gamedata {
+            GameData::Crossword(crossword) => crossword,
+            _ => return Err(Error::NotACrossword),
+        };
+        crossword.diagramless = diagramless;
+
+        Ok(TggFile::assemble(
+            &self.metadata.title,
+            &self.metadata.description,
+            &self.metadata.author,
+            self.metadata.creation_date,
+            GameData::Crossword(crossword),
+            self.extensions,
+        ))
+    }
+
+    pub(crate) fn assemble(
+        title: &str,
+        description: &str,
+        author: &str,
+        creation_date: u32,
+        gamedata: GameData,
+        extensions: Vec<Extension>,
+    ) -> TggFile {
+        let gamedata_bytes = gamedata_to_bytes(&gamedata, &extensions);
+        let gamedata_checksum = calculate_checksum(gamedata_bytes.clone());
+
+        let metadata = Metadata::new(
+            title.to_string(),
+            description.to_string(),
+            author.to_string(),
+            creation_date,
             u16::from_le_bytes(gamedata_checksum),
         );
 
-        let mut bytes = crossword.to_bytes();
+        let mut bytes = gamedata_bytes;
         bytes.extend(metadata.to_bytes());
 
         let file_checksum = calculate_checksum(bytes);
 
         let footer = Footer::new(u16::from_le_bytes(file_checksum));
 
-        let header = Header::new(Game::Crossword, u16::from_le_bytes(file_checksum));
+        let header = Header::new(gamedata.game(), u16::from_le_bytes(file_checksum));
 
-        Ok(TggFile {
+        TggFile {
             header,
             metadata,
-            gamedata: GameData::Crossword(crossword),
+            gamedata,
+            extensions,
             footer,
-        })
+        }
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
@@ -79,42 +188,67 @@ impl TggFile {
 
         bytes.extend(self.header.to_bytes());
         bytes.extend(self.metadata.to_bytes());
-        bytes.extend(self.gamedata.to_bytes());
+        bytes.extend(gamedata_to_bytes(&self.gamedata, &self.extensions));
         bytes.extend(self.footer.to_bytes());
 
         bytes
     }
 
     pub fn get_game_name(&self) -> String {
-        return self.header.game.to_string();
+        self.header.game.to_string()
     }
 
     pub fn get_game(&self) -> &Game {
-        return &self.header.game;
+        &self.header.game
     }
 
     pub fn get_title(&self) -> String {
-        return self.metadata.title.to_string();
+        self.metadata.title.to_string()
     }
 
     pub fn get_description(&self) -> String {
-        return self.metadata.description.to_string();
+        self.metadata.description.to_string()
     }
 
     pub fn get_author(&self) -> String {
-        return self.metadata.author.to_string();
+        self.metadata.author.to_string()
     }
 
     pub fn get_raw_creation_date(&self) -> u32 {
-        return self.metadata.creation_date;
+        self.metadata.creation_date
     }
 
     pub fn get_formatted_creation_date(&self) -> String {
-        return self.metadata.get_date();
+        self.metadata.get_date()
     }
 
     pub fn get_game_data(self) -> GameData {
-        return self.gamedata;
+        self.gamedata
+    }
+
+    pub fn get_crossword(&self) -> Option<&CrosswordData> {
+        match &self.gamedata {
+            GameData::Crossword(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn get_word_search(&self) -> Option<&WordSearchData> {
+        match &self.gamedata {
+            GameData::WordSearch(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn get_extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+
+    pub fn get_extension(&self, key: &str) -> Option<&str> {
+        self.extensions
+            .iter()
+            .find(|extension| extension.key == key)
+            .map(|extension| extension.value.as_str())
     }
 }
 
@@ -145,7 +279,7 @@ impl Header {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Game {
     Crossword,
     WordSearch,
@@ -154,24 +288,25 @@ pub enum Game {
 impl Game {
     pub fn to_byte(&self) -> u8 {
         match self {
-            Game::Crossword => return 0x01,
-            Game::WordSearch => return 0x02,
+            Game::Crossword => 0x01,
+            Game::WordSearch => 0x02,
         }
     }
 
-    pub fn to_string(&self) -> String {
-        match self {
-            Game::Crossword => "Crossword",
-            Game::WordSearch => "Word Search",
+    pub fn from_byte(byte: u8) -> Option<Game> {
+        match byte {
+            0x01 => Some(Game::Crossword),
+            0x02 => Some(Game::WordSearch),
+            _ => None,
         }
-        .to_string()
     }
+}
 
-    pub fn from_byte(byte: u8) -> Option<Game> {
-        match byte {
-            0x01 => return Some(Game::Crossword),
-            0x02 => return Some(Game::WordSearch),
-            _ => return None,
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Game::Crossword => write!(f, "Crossword"),
+            Game::WordSearch => write!(f, "Word Search"),
         }
     }
 }
@@ -202,29 +337,8 @@ impl Metadata {
         }
     }
 
-    pub fn create(
-        title: &str,
-        description: &str,
-        author: &str,
-        gamedata_checksum: u16,
-    ) -> Metadata {
-        let now = SystemTime::now();
-        let timestamp = now
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs() as u32;
-
-        Metadata {
-            title: title.to_string(),
-            description: description.to_string(),
-            author: author.to_string(),
-            creation_date: timestamp,
-            gamedata_checksum,
-        }
-    }
-
     pub fn get_date(&self) -> String {
-        return format_timestamp(self.creation_date);
+        format_timestamp(self.creation_date)
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
@@ -246,14 +360,109 @@ impl Metadata {
 #[derive(Debug)]
 pub enum GameData {
     Crossword(CrosswordData),
+    WordSearch(WordSearchData),
 }
 
 impl GameData {
+    pub fn game(&self) -> Game {
+        match self {
+            GameData::Crossword(_) => Game::Crossword,
+            GameData::WordSearch(_) => Game::WordSearch,
+        }
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         match self {
             GameData::Crossword(data) => data.to_bytes(),
+            GameData::WordSearch(data) => data.to_bytes(),
+        }
+    }
+
+    fn format_version(&self) -> u8 {
+        match self {
+            GameData::Crossword(data) => data.format_version(),
+            GameData::WordSearch(_) => 1,
+        }
+    }
+
+    fn to_bytes_version(&self, version: u8) -> Vec<u8> {
+        match self {
+            GameData::Crossword(data) => data.to_bytes_version(version),
+            GameData::WordSearch(data) => data.to_bytes(),
+        }
+    }
+}
+
+/// Data from another format that can't be represented in TGG, kept so it survives a
+/// round trip back to that format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension {
+    pub key: String,
+    pub value: String,
+}
+
+impl Extension {
+    pub fn new(key: &str, value: &str) -> Extension {
+        Extension {
+            key: key.to_string(),
+            value: value.to_string(),
         }
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend(self.key.as_bytes());
+        bytes.push(0);
+        bytes.extend(self.value.as_bytes());
+        bytes.push(0);
+
+        bytes
+    }
+}
+
+fn gamedata_to_bytes(gamedata: &GameData, extensions: &[Extension]) -> Vec<u8> {
+    let version = if extensions.is_empty() { 1 } else { 2 };
+    let version = gamedata.format_version().max(version);
+    if version == 1 {
+        return gamedata.to_bytes_version(version);
+    }
+
+    let mut bytes = vec![0, version];
+
+    bytes.extend((extensions.len() as u16).to_le_bytes());
+    for extension in extensions {
+        bytes.extend(extension.to_bytes());
+    }
+
+    bytes.extend(gamedata.to_bytes_version(version));
+
+    bytes
+}
+
+fn current_timestamp() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as u32
+}
+
+/// Imported metadata, with a placeholder for a field that's empty since `.tgg` files
+/// can't be loaded without it.
+fn imported_metadata(value: String, field: &str, warnings: &mut Vec<Warning>) -> String {
+    if !value.is_empty() {
+        return value;
+    }
+
+    warnings.push(Warning::MissingMetadata {
+        field: field.to_string(),
+    });
+    match field {
+        "title" => "Untitled",
+        "description" => "No description",
+        _ => "Unknown",
+    }
+    .to_string()
 }
 
 #[derive(Debug)]
@@ -0,0 +1,303 @@
+//! Construction checks for crossword grids, for editorial standards like symmetry and
+//! checking that `CrosswordData::new` doesn't enforce.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordData};
+use crate::entry::Direction;
+
+/// How many of the boxes in each entry have to be shared with a crossing entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checking {
+    /// Boxes may be unchecked.
+    Any,
+    /// No two unchecked boxes in a row, and at least half of every entry is checked.
+    Alternating,
+    /// Every box is part of both an across and a down entry.
+    Full,
+}
+
+/// The rules to apply. `Default` enables none of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    /// The grid looks the same after turning it by 180 degrees.
+    pub rotational_symmetry: bool,
+    /// Every open box can be reached from every other one.
+    pub connectivity: bool,
+    pub min_entry_length: Option<u8>,
+    pub max_entry_length: Option<u8>,
+    pub max_entries: Option<u16>,
+    pub checking: Checking,
+}
+
+impl Default for LintConfig {
+    fn default() -> LintConfig {
+        LintConfig {
+            rotational_symmetry: false,
+            connectivity: false,
+            min_entry_length: None,
+            max_entry_length: None,
+            max_entries: None,
+            checking: Checking::Any,
+        }
+    }
+}
+
+impl LintConfig {
+    /// Standards of 15x15 American daily newspaper puzzles, which have at most 78
+    /// entries. Other grid sizes need their own `max_entries`.
+    pub fn american_15x15() -> LintConfig {
+        LintConfig {
+            rotational_symmetry: true,
+            connectivity: true,
+            min_entry_length: Some(3),
+            max_entry_length: None,
+            max_entries: Some(78),
+            checking: Checking::Full,
+        }
+    }
+
+    /// Standards of British blocked cryptics, which allow unchecked boxes as long as
+    /// they alternate with checked ones.
+    pub fn british_cryptic() -> LintConfig {
+        LintConfig {
+            rotational_symmetry: true,
+            connectivity: true,
+            min_entry_length: Some(3),
+            max_entry_length: None,
+            max_entries: None,
+            checking: Checking::Alternating,
+        }
+    }
+}
+
+/// A broken rule. Entry findings point at the first box of the entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintFinding {
+    /// A solid box whose rotated counterpart is open.
+    AsymmetricBlock {
+        row: u8,
+        column: u8,
+    },
+    /// A bar on the right or bottom edge of a box without a bar on the rotated edge.
+    AsymmetricBar {
+        row: u8,
+        column: u8,
+    },
+    /// An open box that can't be reached from the first open box.
+    DisconnectedBox {
+        row: u8,
+        column: u8,
+    },
+    ShortEntry {
+        row: u8,
+        column: u8,
+        direction: Direction,
+        length: u8,
+    },
+    LongEntry {
+        row: u8,
+        column: u8,
+        direction: Direction,
+        length: u8,
+    },
+    TooManyEntries {
+        count: u16,
+        max: u16,
+    },
+    UncheckedBox {
+        row: u8,
+        column: u8,
+    },
+    /// The second of two unchecked boxes in a row.
+    ConsecutiveUncheckedBoxes {
+        row: u8,
+        column: u8,
+        direction: Direction,
+    },
+    UnderCheckedEntry {
+        row: u8,
+        column: u8,
+        direction: Direction,
+        checked: u8,
+        length: u8,
+    },
+}
+
+pub fn lint(crossword: &CrosswordData, config: &LintConfig) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+
+    if config.rotational_symmetry {
+        check_symmetry(crossword, &mut findings);
+    }
+
+    if config.connectivity {
+        check_connectivity(crossword, &mut findings);
+    }
+
+    let entries = crossword.entries();
+
+    for entry in &entries {
+        let (row, column) = entry.start;
+        let (direction, length) = (entry.direction, entry.length);
+
+        if config.min_entry_length.is_some_and(|min| length < min) {
+            findings.push(LintFinding::ShortEntry {
+                row,
+                column,
+                direction,
+                length,
+            });
+        }
+
+        if config.max_entry_length.is_some_and(|max| length > max) {
+            findings.push(LintFinding::LongEntry {
+                row,
+                column,
+                direction,
+                length,
+            });
+        }
+    }
+
+    if let Some(max) = config.max_entries {
+        if entries.len() > max as usize {
+            findings.push(LintFinding::TooManyEntries {
+                count: entries.len() as u16,
+                max,
+            });
+        }
+    }
+
+    if config.checking == Checking::Any {
+        return findings;
+    }
+
+    // How many entries each box is part of, a box is checked when it's part of two
+    let mut counts = vec![vec![0u8; crossword.width as usize]; crossword.height as usize];
+    for entry in &entries {
+        for (row, column) in &entry.cells {
+            counts[*row as usize][*column as usize] += 1;
+        }
+    }
+
+    for (row, boxes) in crossword.crossword_data.iter().enumerate() {
+        for (column, crossword_box) in boxes.iter().enumerate() {
+            let count = counts[row][column];
+            // With alternating checking only boxes outside of any entry are a problem
+            let unchecked = match config.checking {
+                Checking::Full => count < 2,
+                _ => count == 0,
+            };
+
+            if crossword_box.value.is_open() && unchecked {
+                findings.push(LintFinding::UncheckedBox {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+        }
+    }
+
+    if config.checking == Checking::Alternating {
+        for entry in &entries {
+            let is_checked = |&(row, column): &(u8, u8)| counts[row as usize][column as usize] > 1;
+
+            for pair in entry.cells.windows(2) {
+                if !is_checked(&pair[0]) && !is_checked(&pair[1]) {
+                    findings.push(LintFinding::ConsecutiveUncheckedBoxes {
+                        row: pair[1].0,
+                        column: pair[1].1,
+                        direction: entry.direction,
+                    });
+                }
+            }
+
+            let checked = entry.cells.iter().filter(|cell| is_checked(cell)).count() as u8;
+            if (checked as u16) * 2 < entry.length as u16 {
+                findings.push(LintFinding::UnderCheckedEntry {
+                    row: entry.start.0,
+                    column: entry.start.1,
+                    direction: entry.direction,
+                    checked,
+                    length: entry.length,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+fn check_symmetry(crossword: &CrosswordData, findings: &mut Vec<LintFinding>) {
+    let grid = &crossword.crossword_data;
+    let (height, width) = (crossword.height as usize, crossword.width as usize);
+
+    for (row, boxes) in grid.iter().enumerate() {
+        for (column, crossword_box) in boxes.iter().enumerate() {
+            let counterpart = &grid[height - 1 - row][width - 1 - column];
+            // Void boxes shape the grid, which doesn't have to be symmetric
+            if crossword_box.value == CrosswordBoxValue::Solid && counterpart.value.is_open() {
+                findings.push(LintFinding::AsymmetricBlock {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+
+            // Turned around, the right edge of a box becomes the left edge of its
+            // counterpart, which is the right edge of the box before that
+            let bars = crossword_box.bars;
+            let right_missing =
+                column + 1 < width && !grid[height - 1 - row][width - 2 - column].bars.right;
+            let bottom_missing =
+                row + 1 < height && !grid[height - 2 - row][width - 1 - column].bars.bottom;
+            if (bars.right && right_missing) || (bars.bottom && bottom_missing) {
+                findings.push(LintFinding::AsymmetricBar {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+        }
+    }
+}
+
+fn check_connectivity(crossword: &CrosswordData, findings: &mut Vec<LintFinding>) {
+    let grid = &crossword.crossword_data;
+    let is_open = |row: usize, column: usize| grid[row][column].value.is_open();
+    let (height, width) = (crossword.height as usize, crossword.width as usize);
+
+    let mut reached = vec![vec![false; width]; height];
+    let start = (0..height)
+        .flat_map(|row| (0..width).map(move |column| (row, column)))
+        .find(|&(row, column)| is_open(row, column));
+
+    let mut stack: Vec<(usize, usize)> = start.into_iter().collect();
+    while let Some((row, column)) = stack.pop() {
+        if reached[row][column] || !is_open(row, column) {
+            continue;
+        }
+        reached[row][column] = true;
+
+        if row > 0 {
+            stack.push((row - 1, column));
+        }
+        if row + 1 < height {
+            stack.push((row + 1, column));
+        }
+        if column > 0 {
+            stack.push((row, column - 1));
+        }
+        if column + 1 < width {
+            stack.push((row, column + 1));
+        }
+    }
+
+    for (row, cells) in reached.iter().enumerate() {
+        for (column, reached) in cells.iter().enumerate() {
+            if is_open(row, column) && !reached {
+                findings.push(LintFinding::DisconnectedBox {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     crossword::CrosswordData,
     utils::{calculate_checksum, extract_cstring_with_offset},
-    Error, Footer, Game, GameData, Header, Metadata, TggFile,
+    word_search::WordSearchData,
+    Error, Extension, Footer, Game, GameData, Header, Metadata, TggFile, FORMAT_VERSION,
 };
 
 pub fn load(bytes: Vec<u8>) -> Result<TggFile, Error> {
@@ -88,17 +89,21 @@ pub fn load(bytes: Vec<u8>) -> Result<TggFile, Error> {
         return Err(Error::GameDataIsEmpty);
     }
 
+    let (version, extensions, game_data) = split_gamedata(game_data)?;
+    if game_data.is_empty() {
+        return Err(Error::GameDataIsEmpty);
+    }
+
     let gamedata: GameData = match game {
         Game::Crossword => {
-            let crossword_data = match CrosswordData::load(game_data) {
-                Ok(crossword_data) => crossword_data,
-                Err(err) => return Err(err),
-            };
+            let crossword_data = CrosswordData::load_version(game_data, version)?;
 
             GameData::Crossword(crossword_data)
         }
         Game::WordSearch => {
-            unimplemented!()
+            let word_search_data = WordSearchData::load(game_data)?;
+
+            GameData::WordSearch(word_search_data)
         }
     };
 
@@ -110,6 +115,50 @@ pub fn load(bytes: Vec<u8>) -> Result<TggFile, Error> {
         header,
         metadata,
         gamedata,
+        extensions,
         footer,
     })
 }
+
+/// Splits versioned game data into its version, its extension area and the game data
+/// itself.
+fn split_gamedata(bytes: &[u8]) -> Result<(u8, Vec<Extension>, &[u8]), Error> {
+    // Version 1 game data starts with the width, which is never zero
+    if bytes[0] != 0x00 {
+        return Ok((1, Vec::new(), bytes));
+    }
+
+    if bytes.len() < 4 {
+        return Err(Error::UnexpectedEndOfFile);
+    }
+
+    let version = bytes[1];
+    if !(2..=FORMAT_VERSION).contains(&version) {
+        return Err(Error::UnsupportedFormatVersion { found: version });
+    }
+
+    let total_extensions = u16::from_le_bytes([bytes[2], bytes[3]]);
+    let mut offset = 4;
+
+    let mut extensions = Vec::new();
+    for _ in 0..total_extensions {
+        let (key, new_offset) = extract_utf8_cstring(bytes, offset)?;
+        let (value, new_offset) = extract_utf8_cstring(bytes, new_offset)?;
+        extensions.push(Extension { key, value });
+        offset = new_offset;
+    }
+
+    Ok((version, extensions, &bytes[offset..]))
+}
+
+fn extract_utf8_cstring(bytes: &[u8], start: usize) -> Result<(String, usize), Error> {
+    let remaining = bytes.get(start..).unwrap_or_default();
+    let length = match remaining.iter().position(|&byte| byte == 0x00) {
+        Some(length) => length,
+        None => return Err(Error::UnexpectedEndOfFile),
+    };
+
+    let string = String::from_utf8_lossy(&remaining[..length]).to_string();
+
+    Ok((string, start + length + 1))
+}
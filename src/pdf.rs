@@ -0,0 +1,600 @@
+//! Printable PDF export of a crossword and its answer key.
+//!
+//! The document only uses the standard Helvetica fonts, which every PDF reader ships, so
+//! nothing has to be embedded. Text is measured with the Helvetica metrics below to wrap
+//! clues and center letters.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordClue, CrosswordData};
+use crate::render::RenderMode;
+use crate::svg::wrap;
+use crate::utils::number;
+use crate::{Error, GameData, TggFile};
+
+const MARGIN: f32 = 54.0;
+const COLUMN_GAP: f32 = 24.0;
+const MAX_CELL_SIZE: f32 = 36.0;
+/// Share of the page height the grid may use on the puzzle page.
+const MAX_GRID_HEIGHT: f32 = 0.5;
+
+const TITLE_SIZE: f32 = 20.0;
+const BYLINE_SIZE: f32 = 11.0;
+const CLUE_SIZE: f32 = 10.0;
+const HEADING_SIZE: f32 = 12.0;
+const BAR_WIDTH: f32 = 2.5;
+
+/// Widths of the printable ASCII characters in Helvetica, in 1/1000 of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Widths of the printable ASCII characters in Helvetica-Bold.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageSize {
+    Letter,
+    A4,
+}
+
+impl PageSize {
+    /// Width and height in points.
+    fn dimensions(&self) -> (f32, f32) {
+        match self {
+            PageSize::Letter => (612.0, 792.0),
+            PageSize::A4 => (595.0, 842.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    pub page_size: PageSize,
+    /// Adds a final page with the filled in solution.
+    pub answer_key: bool,
+}
+
+impl Default for PdfOptions {
+    fn default() -> PdfOptions {
+        PdfOptions {
+            page_size: PageSize::Letter,
+            answer_key: true,
+        }
+    }
+}
+
+impl TggFile {
+    pub fn to_pdf(&self, options: &PdfOptions) -> Result<Vec<u8>, Error> {
+        let crossword = match &self.gamedata {
+            GameData::Crossword(crossword) => crossword,
+            _ => return Err(Error::NotACrossword),
+        };
+
+        let (page_width, page_height) = options.page_size.dimensions();
+        let content_width = page_width - MARGIN * 2.0;
+        let cell_size = (content_width / crossword.width as f32)
+            .min(page_height * MAX_GRID_HEIGHT / crossword.height as f32)
+            .min(MAX_CELL_SIZE);
+
+        let mut pages = Vec::new();
+        let mut page = Page::new(page_height);
+        let mut y = MARGIN;
+
+        page.text(
+            MARGIN,
+            y + TITLE_SIZE,
+            Font::Bold,
+            TITLE_SIZE,
+            &self.metadata.title,
+        );
+        y += TITLE_SIZE + 8.0;
+
+        let byline = format!(
+            "By {}  |  {}",
+            self.metadata.author,
+            self.metadata.get_date()
+        );
+        page.text(MARGIN, y + BYLINE_SIZE, Font::Regular, BYLINE_SIZE, &byline);
+        y += BYLINE_SIZE + 6.0;
+
+        for line in wrap_measured(
+            &self.metadata.description,
+            Font::Regular,
+            CLUE_SIZE,
+            content_width,
+        ) {
+            page.text(MARGIN, y + CLUE_SIZE, Font::Regular, CLUE_SIZE, &line);
+            y += CLUE_SIZE * 1.3;
+        }
+        y += 12.0;
+
+        let grid_x = (page_width - cell_size * crossword.width as f32) / 2.0;
+        draw_grid(
+            &mut page,
+            crossword,
+            grid_x,
+            y,
+            cell_size,
+            RenderMode::Blank,
+        );
+        y += cell_size * crossword.height as f32 + 24.0;
+
+        // Clues flow down the left column, then the right one, then onto new pages
+        let column_width = (content_width - COLUMN_GAP) / 2.0;
+        let mut column = 0;
+        let mut flow = ClueFlow {
+            top: y,
+            y,
+            bottom: page_height - MARGIN,
+        };
+
+        for (heading, clues) in [
+            ("Across", &crossword.horizontal_clues),
+            ("Down", &crossword.vertical_clues),
+        ] {
+            let mut blocks = vec![ClueBlock::Heading(heading)];
+            blocks.extend(clues.iter().map(ClueBlock::Clue));
+
+            for block in blocks {
+                let lines = block.lines(column_width);
+                let height = block.height(&lines);
+
+                if !flow.fits(height) {
+                    column += 1;
+                    if column == 2 {
+                        pages.push(page);
+                        page = Page::new(page_height);
+                        column = 0;
+                        flow.top = MARGIN;
+                    }
+                    flow.y = flow.top;
+                }
+
+                let x = MARGIN + column as f32 * (column_width + COLUMN_GAP);
+                block.draw(&mut page, x, flow.y, &lines);
+                flow.y += height;
+            }
+        }
+        pages.push(page);
+
+        if options.answer_key {
+            let mut page = Page::new(page_height);
+            let mut y = MARGIN;
+
+            page.text(MARGIN, y + TITLE_SIZE, Font::Bold, TITLE_SIZE, "Answer Key");
+            y += TITLE_SIZE + 8.0;
+            page.text(
+                MARGIN,
+                y + BYLINE_SIZE,
+                Font::Regular,
+                BYLINE_SIZE,
+                &self.metadata.title,
+            );
+            y += BYLINE_SIZE + 18.0;
+
+            draw_grid(
+                &mut page,
+                crossword,
+                grid_x,
+                y,
+                cell_size,
+                RenderMode::Solution,
+            );
+            pages.push(page);
+        }
+
+        Ok(write_document(self, options.page_size, &pages))
+    }
+}
+
+struct ClueFlow {
+    top: f32,
+    y: f32,
+    bottom: f32,
+}
+
+impl ClueFlow {
+    fn fits(&self, height: f32) -> bool {
+        // A block that doesn't fit an empty column is drawn anyway
+        self.y == self.top || self.y + height <= self.bottom
+    }
+}
+
+enum ClueBlock<'a> {
+    Heading(&'a str),
+    Clue(&'a CrosswordClue),
+}
+
+impl ClueBlock<'_> {
+    const NUMBER_WIDTH: f32 = 22.0;
+
+    fn lines(&self, width: f32) -> Vec<String> {
+        match self {
+            ClueBlock::Heading(heading) => vec![heading.to_string()],
+            ClueBlock::Clue(clue) => wrap_measured(
+                &clue.full_text(),
+                Font::Regular,
+                CLUE_SIZE,
+                width - Self::NUMBER_WIDTH,
+            ),
+        }
+    }
+
+    fn height(&self, lines: &[String]) -> f32 {
+        match self {
+            ClueBlock::Heading(_) => HEADING_SIZE * 1.8,
+            ClueBlock::Clue(_) => lines.len() as f32 * CLUE_SIZE * 1.3 + 3.0,
+        }
+    }
+
+    fn draw(&self, page: &mut Page, x: f32, y: f32, lines: &[String]) {
+        match self {
+            ClueBlock::Heading(heading) => {
+                page.text(x, y + HEADING_SIZE, Font::Bold, HEADING_SIZE, heading)
+            }
+            ClueBlock::Clue(clue) => {
+                let number = clue.number.to_string();
+                let number_x =
+                    x + Self::NUMBER_WIDTH - 6.0 - text_width(&number, Font::Bold, CLUE_SIZE);
+                page.text(number_x, y + CLUE_SIZE, Font::Bold, CLUE_SIZE, &number);
+
+                for (index, line) in lines.iter().enumerate() {
+                    let line_y = y + CLUE_SIZE + index as f32 * CLUE_SIZE * 1.3;
+                    page.text(
+                        x + Self::NUMBER_WIDTH,
+                        line_y,
+                        Font::Regular,
+                        CLUE_SIZE,
+                        line,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn draw_grid(
+    page: &mut Page,
+    crossword: &CrosswordData,
+    x: f32,
+    y: f32,
+    cell_size: f32,
+    mode: RenderMode,
+) {
+    // Diagramless solvers only get the dimensions
+    if crossword.diagramless && mode == RenderMode::Blank {
+        return draw_grid(page, &crossword.without_diagram(), x, y, cell_size, mode);
+    }
+
+    let number_size = (cell_size * 0.28).max(5.0);
+    let letter_size = cell_size * 0.6;
+
+    for (row, cells) in crossword.crossword_data.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            let cell_x = x + column as f32 * cell_size;
+            let cell_y = y + row as f32 * cell_size;
+
+            match cell.value {
+                CrosswordBoxValue::Void => continue,
+                CrosswordBoxValue::Solid => {
+                    page.rectangle(cell_x, cell_y, cell_size, cell_size, true, 0.5);
+                    continue;
+                }
+                _ => {}
+            }
+            match cell.style.shade {
+                Some(shade) => page.shaded_rectangle(cell_x, cell_y, cell_size, cell_size, shade),
+                None => page.rectangle(cell_x, cell_y, cell_size, cell_size, false, 0.5),
+            }
+            if cell.style.circled {
+                let radius = cell_size * 0.45;
+                page.circle(cell_x + cell_size / 2.0, cell_y + cell_size / 2.0, radius);
+            }
+            if cell.style.bold_border {
+                let inset = cell_size * 0.05;
+                let size = cell_size - inset * 2.0;
+                page.rectangle(cell_x + inset, cell_y + inset, size, size, false, 2.0);
+            }
+
+            if cell.number != 0 {
+                page.text(
+                    cell_x + cell_size * 0.06,
+                    cell_y + number_size + cell_size * 0.04,
+                    Font::Regular,
+                    number_size,
+                    &cell.number.to_string(),
+                );
+            }
+
+            if let (Some(letter), RenderMode::Solution) = (cell.value.content(), mode) {
+                // Rebuses shrink to fit the box
+                let width = text_width(&letter, Font::Regular, letter_size);
+                let letter_size = if width > cell_size * 0.9 {
+                    letter_size * cell_size * 0.9 / width
+                } else {
+                    letter_size
+                };
+                let letter_x =
+                    cell_x + (cell_size - text_width(&letter, Font::Regular, letter_size)) / 2.0;
+                page.text(
+                    letter_x,
+                    cell_y + cell_size * 0.55 + letter_size * 0.35,
+                    Font::Regular,
+                    letter_size,
+                    &letter,
+                );
+            }
+        }
+    }
+
+    // Bars go over the boxes on both sides of them, so they're drawn last
+    for (row, cells) in crossword.crossword_data.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            let cell_x = x + column as f32 * cell_size;
+            let cell_y = y + row as f32 * cell_size;
+
+            if cell.bars.right {
+                let bar_x = cell_x + cell_size;
+                page.line(bar_x, cell_y, bar_x, cell_y + cell_size, BAR_WIDTH);
+            }
+            if cell.bars.bottom {
+                let bar_y = cell_y + cell_size;
+                page.line(cell_x, bar_y, cell_x + cell_size, bar_y, BAR_WIDTH);
+            }
+        }
+    }
+
+    // A shaped grid is outlined by its boxes
+    if !crossword.is_shaped() {
+        page.rectangle(
+            x,
+            y,
+            cell_size * crossword.width as f32,
+            cell_size * crossword.height as f32,
+            false,
+            1.5,
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    fn char_width(&self, c: char) -> u16 {
+        let widths = match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        };
+
+        match c as u32 {
+            code @ 32..=126 => widths[(code - 32) as usize],
+            _ => 556,
+        }
+    }
+}
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    text.chars().map(|c| font.char_width(c) as f32).sum::<f32>() * size / 1000.0
+}
+
+/// Wraps text so every line fits in `width` points, using the character count of the
+/// widest fitting line as a starting point and shortening lines that still overflow.
+fn wrap_measured(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let average = text_width(text, font, size) / text.chars().count() as f32;
+    let mut max_chars = (width / average) as usize;
+
+    loop {
+        let lines = wrap(text, max_chars.max(1));
+        let fits = lines
+            .iter()
+            .all(|line| text_width(line, font, size) <= width || !line.contains(' '));
+        if fits || max_chars <= 1 {
+            return lines;
+        }
+        max_chars -= 1;
+    }
+}
+
+/// Drawing operations for one page, taking coordinates from the top left corner.
+struct Page {
+    height: f32,
+    content: String,
+}
+
+impl Page {
+    fn new(height: f32) -> Page {
+        Page {
+            height,
+            content: String::new(),
+        }
+    }
+
+    /// Draws text with its baseline at `y`.
+    fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        self.content.push_str(&format!(
+            "BT /{} {} Tf {} {} Td ({}) Tj ET\n",
+            font.resource(),
+            number(size),
+            number(x),
+            number(self.height - y),
+            escape(text)
+        ));
+    }
+
+    fn rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, fill: bool, line_width: f32) {
+        self.content.push_str(&format!(
+            "{} w {} {} {} {} re {}\n",
+            number(line_width),
+            number(x),
+            number(self.height - y - height),
+            number(width),
+            number(height),
+            if fill { "B" } else { "S" }
+        ));
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, line_width: f32) {
+        self.content.push_str(&format!(
+            "{} w {} {} m {} {} l S\n",
+            number(line_width),
+            number(x1),
+            number(self.height - y1),
+            number(x2),
+            number(self.height - y2)
+        ));
+    }
+
+    /// Draws a box filled with a color, restoring the black fill afterwards.
+    fn shaded_rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, color: [u8; 3]) {
+        let [red, green, blue] = color.map(|channel| number(channel as f32 / 255.0));
+        self.content
+            .push_str(&format!("q {} {} {} rg\n", red, green, blue));
+        self.rectangle(x, y, width, height, true, 0.5);
+        self.content.push_str("Q\n");
+    }
+
+    /// Strokes a circle, approximated by four Bézier curves.
+    fn circle(&mut self, center_x: f32, center_y: f32, radius: f32) {
+        // Distance of the control points from the ends of each quarter
+        let k = radius * 0.5523;
+        let x = center_x;
+        let y = self.height - center_y;
+
+        let mut path = format!("0.5 w {} {} m\n", number(x + radius), number(y));
+        let curves = [
+            [(x + radius, y + k), (x + k, y + radius), (x, y + radius)],
+            [(x - k, y + radius), (x - radius, y + k), (x - radius, y)],
+            [(x - radius, y - k), (x - k, y - radius), (x, y - radius)],
+            [(x + k, y - radius), (x + radius, y - k), (x + radius, y)],
+        ];
+        for points in curves {
+            for (point_x, point_y) in points {
+                path.push_str(&format!("{} {} ", number(point_x), number(point_y)));
+            }
+            path.push_str("c\n");
+        }
+        path.push_str("S\n");
+
+        self.content.push_str(&path);
+    }
+}
+
+fn write_document(tgg_file: &TggFile, page_size: PageSize, pages: &[Page]) -> Vec<u8> {
+    let (page_width, page_height) = page_size.dimensions();
+
+    // Objects 1 to 5 are fixed, every page then gets a page object and a content stream
+    let mut objects: Vec<String> = Vec::new();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 6 + index * 2).collect();
+
+    objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+    objects.push(format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        page_ids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<String>>()
+            .join(" "),
+        pages.len()
+    ));
+    objects.push(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    );
+    objects.push(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    );
+    objects.push(format!(
+        "<< /Title ({}) /Author ({}) /Subject ({}) /Producer (tgg) >>",
+        escape(&tgg_file.metadata.title),
+        escape(&tgg_file.metadata.author),
+        escape(&tgg_file.metadata.description)
+    ));
+
+    for (index, page) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            number(page_width),
+            number(page_height),
+            page_ids[index] + 1
+        ));
+
+        // Content streams are plain ASCII since `escape` encodes everything else
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            page.content.len(),
+            page.content
+        ));
+    }
+
+    let mut bytes = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::new();
+
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(bytes.len());
+        bytes.extend(format!("{} 0 obj\n", index + 1).as_bytes());
+        bytes.extend(object.as_bytes());
+        bytes.extend(b"\nendobj\n");
+    }
+
+    let xref_offset = bytes.len();
+    bytes.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        bytes.extend(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    bytes.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+
+    bytes
+}
+
+/// Escapes a PDF string. Characters outside of ASCII are written as octal escapes of
+/// their WinAnsi code, or `?` when WinAnsi can't represent them.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            ' '..='~' => escaped.push(c),
+            '\u{A0}'..='\u{FF}' => escaped.push_str(&format!("\\{:03o}", c as u32)),
+            _ => escaped.push('?'),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,332 @@
+//! A player's progress on a crossword, saved separately from the puzzle so it can be
+//! synced between apps.
+//!
+//! The save format follows the puzzle format: an ID, a version and a checksum, then the
+//! fingerprint of the puzzle, the time played and two bytes per box, then the checksum
+//! again as a footer. Like in the puzzle format, rebus guesses are stored as a
+//! placeholder byte with their content following the boxes. The fingerprint and the
+//! time played are big-endian, like the creation date of a puzzle.
+//!
+//! Version 2 adds blocks placed by the player in diagramless crosswords. Saves without
+//! any are still written as version 1.
+
+use crate::crossword::{validate_rebus, CrosswordData};
+use crate::utils::{calculate_checksum, extract_cstring_with_offset};
+use crate::Error;
+
+const ID: &str = "TalonGamesSave";
+
+/// Latest version of the save format.
+const STATE_VERSION: u8 = 2;
+
+/// ID, version and checksum.
+const HEADER_LENGTH: usize = 17;
+
+/// Guess byte standing in for a rebus guess.
+const REBUS: u8 = 0x01;
+
+const PENCIL: u8 = 0b001;
+const CHECKED: u8 = 0b010;
+const REVEALED: u8 = 0b100;
+const BLOCK: u8 = 0b1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerCell {
+    /// A letter, or several for a rebus.
+    pub guess: Option<String>,
+    /// The guess is only tentative.
+    pub pencil: bool,
+    /// The guess has been checked against the solution.
+    pub checked: bool,
+    /// The solution has been shown for this box.
+    pub revealed: bool,
+    /// The player placed a block here, which only happens in diagramless crosswords.
+    pub block: bool,
+}
+
+impl PlayerCell {
+    fn to_bytes(&self) -> [u8; 2] {
+        let mut flags = 0;
+        if self.pencil {
+            flags |= PENCIL;
+        }
+        if self.checked {
+            flags |= CHECKED;
+        }
+        if self.revealed {
+            flags |= REVEALED;
+        }
+        if self.block {
+            flags |= BLOCK;
+        }
+
+        let guess = match &self.guess {
+            None => 0x20,
+            Some(guess) if guess.len() == 1 => guess.as_bytes()[0],
+            Some(_) => REBUS,
+        };
+
+        [guess, flags]
+    }
+
+    /// Reads a box, taking rebus guesses from `rebuses` in order.
+    fn from_bytes(
+        guess: u8,
+        flags: u8,
+        version: u8,
+        rebuses: &mut impl Iterator<Item = String>,
+    ) -> Result<PlayerCell, Error> {
+        let guess = match guess {
+            0x20 => None,
+            REBUS => {
+                let rebus = rebuses.next().ok_or(Error::UnexpectedEndOfFile)?;
+                validate_rebus(&rebus)?;
+                Some(rebus)
+            }
+            b if b.is_ascii_graphic() && !b.is_ascii_lowercase() => Some((b as char).to_string()),
+            _ => return Err(Error::InvalidPlayerGuessByte { found: guess }),
+        };
+        let known = if version >= 2 {
+            PENCIL | CHECKED | REVEALED | BLOCK
+        } else {
+            PENCIL | CHECKED | REVEALED
+        };
+        if flags & !known != 0 {
+            return Err(Error::InvalidPlayerFlags { found: flags });
+        }
+
+        Ok(PlayerCell {
+            guess,
+            pencil: flags & PENCIL != 0,
+            checked: flags & CHECKED != 0,
+            revealed: flags & REVEALED != 0,
+            block: flags & BLOCK != 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerState {
+    /// Fingerprint of the puzzle this progress belongs to.
+    pub fingerprint: u64,
+    pub width: u8,
+    pub height: u8,
+    pub cells: Vec<Vec<PlayerCell>>,
+    pub elapsed_seconds: u32,
+}
+
+impl PlayerState {
+    /// Starts a new solve of the puzzle, with no guesses.
+    pub fn new(crossword: &CrosswordData) -> PlayerState {
+        PlayerState {
+            fingerprint: crossword.fingerprint(),
+            width: crossword.width,
+            height: crossword.height,
+            cells: vec![
+                vec![PlayerCell::default(); crossword.width as usize];
+                crossword.height as usize
+            ],
+            elapsed_seconds: 0,
+        }
+    }
+
+    /// Whether this progress belongs to the puzzle.
+    pub fn matches(&self, crossword: &CrosswordData) -> bool {
+        self.fingerprint == crossword.fingerprint()
+    }
+
+    pub fn cell(&self, row: u8, column: u8) -> Option<&PlayerCell> {
+        self.cells
+            .get(row as usize)
+            .and_then(|cells| cells.get(column as usize))
+    }
+
+    pub(crate) fn cell_mut(&mut self, row: u8, column: u8) -> Result<&mut PlayerCell, Error> {
+        self.cells
+            .get_mut(row as usize)
+            .and_then(|cells| cells.get_mut(column as usize))
+            .ok_or(Error::BoxOutOfRange { row, column })
+    }
+
+    /// Sets or clears the guess in a box, which may be a rebus. The box is no longer
+    /// marked as checked, since the new guess hasn't been.
+    pub fn set_guess(
+        &mut self,
+        row: u8,
+        column: u8,
+        guess: Option<&str>,
+        pencil: bool,
+    ) -> Result<(), Error> {
+        if let Some(guess) = guess {
+            validate_rebus(guess)?;
+        }
+
+        let cell = self.cell_mut(row, column)?;
+        cell.guess = guess.map(str::to_string);
+        cell.pencil = pencil && guess.is_some();
+        cell.checked = false;
+        cell.block = false;
+
+        Ok(())
+    }
+
+    /// Places or removes a block in a diagramless crossword, clearing the guess in the
+    /// box.
+    pub fn set_block(&mut self, row: u8, column: u8, block: bool) -> Result<(), Error> {
+        let cell = self.cell_mut(row, column)?;
+        cell.guess = None;
+        cell.pencil = false;
+        cell.checked = false;
+        cell.block = block;
+
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body = self.body_to_bytes();
+        let checksum = calculate_checksum(body.clone());
+
+        // Only saves with blocks need version 2
+        let version = if self.cells.iter().flatten().any(|cell| cell.block) {
+            STATE_VERSION
+        } else {
+            1
+        };
+
+        let mut bytes = Vec::new();
+        bytes.extend(ID.as_bytes());
+        bytes.push(version);
+        bytes.extend(checksum);
+        bytes.extend(body);
+        bytes.extend(checksum);
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<PlayerState, Error> {
+        if bytes.len() < HEADER_LENGTH + 2 {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+
+        if &bytes[0..ID.len()] != ID.as_bytes() {
+            return Err(Error::InvalidID);
+        }
+
+        let version = bytes[ID.len()];
+        if version == 0 || version > STATE_VERSION {
+            return Err(Error::UnsupportedFormatVersion { found: version });
+        }
+
+        let header_checksum = u16::from_le_bytes([bytes[15], bytes[16]]);
+        let footer_checksum = u16::from_le_bytes([bytes[bytes.len() - 2], bytes[bytes.len() - 1]]);
+        let body = &bytes[HEADER_LENGTH..bytes.len() - 2];
+
+        let calculated_checksum = u16::from_le_bytes(calculate_checksum(body.to_vec()));
+        if header_checksum != calculated_checksum {
+            return Err(Error::HeaderChecksumMismatch {
+                expected: header_checksum,
+                found: calculated_checksum,
+            });
+        }
+        if header_checksum != footer_checksum {
+            return Err(Error::FooterChecksumMismatch {
+                expected: header_checksum,
+                found: footer_checksum,
+            });
+        }
+
+        // Fingerprint, time played, width and height
+        if body.len() < 14 {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+        let fingerprint = u64::from_be_bytes(body[0..8].try_into().unwrap());
+        let elapsed_seconds = u32::from_be_bytes(body[8..12].try_into().unwrap());
+        let width = body[12];
+        let height = body[13];
+
+        if width == 0 || height == 0 {
+            return Err(Error::WidthOrHeightIsZero);
+        }
+
+        let cells_end = 14 + width as usize * height as usize * 2;
+        if body.len() < cells_end {
+            return Err(Error::NotEnoughPlayerStateBytes {
+                expected: cells_end as u32,
+                found: body.len() as u32,
+            });
+        }
+
+        let mut rebus_offset = cells_end;
+        let mut rebuses = std::iter::from_fn(|| {
+            if rebus_offset >= body.len() {
+                return None;
+            }
+            let (rebus, new_offset) = extract_cstring_with_offset(body, rebus_offset);
+            rebus_offset = new_offset;
+            Some(rebus)
+        });
+
+        let cells = body[14..cells_end]
+            .chunks(width as usize * 2)
+            .map(|row| {
+                row.chunks(2)
+                    .map(|cell| PlayerCell::from_bytes(cell[0], cell[1], version, &mut rebuses))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        if rebuses.next().is_some() {
+            return Err(Error::NotEnoughPlayerStateBytes {
+                expected: cells_end as u32,
+                found: body.len() as u32,
+            });
+        }
+
+        Ok(PlayerState {
+            fingerprint,
+            width,
+            height,
+            cells,
+            elapsed_seconds,
+        })
+    }
+
+    fn body_to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(self.fingerprint.to_be_bytes());
+        bytes.extend(self.elapsed_seconds.to_be_bytes());
+        bytes.push(self.width);
+        bytes.push(self.height);
+
+        for cell in self.cells.iter().flatten() {
+            bytes.extend(cell.to_bytes());
+        }
+
+        for guess in self
+            .cells
+            .iter()
+            .flatten()
+            .filter_map(|cell| cell.guess.as_ref())
+        {
+            if guess.len() > 1 {
+                bytes.extend(guess.as_bytes());
+                bytes.push(0);
+            }
+        }
+
+        bytes
+    }
+}
+
+impl CrosswordData {
+    /// Identifies the puzzle by its grid and clues, so saved progress can't be loaded
+    /// into a different puzzle. This is a 64-bit FNV-1a hash of the game data as it's
+    /// stored in a file.
+    pub fn fingerprint(&self) -> u64 {
+        self.to_bytes_version(self.format_version())
+            .iter()
+            .fold(0xcbf29ce484222325, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+}
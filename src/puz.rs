@@ -0,0 +1,619 @@
+//! Across Lite `.puz` import and export.
+//!
+//! `.puz` files don't store box numbers, they are derived from the block pattern, and the
+//! clues are stored as one list sorted by number with the across clue before the down
+//! clue. Strings are ISO-8859-1 encoded. Circled boxes are kept in the `GEXT` section,
+//! there is no place for shading or bold borders. Void boxes are written as blocks.
+//! Diagramless crosswords are marked by the puzzle type and get a player grid without
+//! blocks.
+//! Enumerations are written at the end of the clue text. They're only read back when they
+//! add up to the length of the answer, so a clue like `Apollo ___ (11)` keeps its number.
+
+use crate::crossword::{
+    standard_numbering, starts_horizontal_entry, starts_vertical_entry, validate_rebus, CellStyle,
+    CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData,
+};
+use crate::entry::Direction;
+use crate::{current_timestamp, imported_metadata, Error, GameData, TggFile, Warning};
+
+const MAGIC: &[u8] = b"ACROSS&DOWN\0";
+const VERSION: &[u8] = b"1.3\0";
+const MASK: &[u8] = b"ICHEATED";
+const HEADER_LENGTH: usize = 0x34;
+const BLOCK: u8 = b'.';
+/// Across Lite also marks blocks of diagramless solutions with a colon.
+const DIAGRAMLESS_BLOCK: u8 = b':';
+const BLANK: u8 = b'-';
+const CIRCLED: u8 = 0x80;
+const NORMAL_PUZZLE: u16 = 0x0001;
+const DIAGRAMLESS_PUZZLE: u16 = 0x0401;
+
+impl TggFile {
+    pub fn from_puz(bytes: &[u8]) -> Result<(TggFile, Vec<Warning>), Error> {
+        parse(bytes)
+    }
+
+    pub fn to_puz(&self) -> Result<(Vec<u8>, Vec<Warning>), Error> {
+        match &self.gamedata {
+            GameData::Crossword(crossword) => write(self, crossword),
+            _ => Err(Error::NotACrossword),
+        }
+    }
+}
+
+/// The strings section of a `.puz` file, still encoded as ISO-8859-1.
+struct PuzStrings {
+    title: Vec<u8>,
+    author: Vec<u8>,
+    copyright: Vec<u8>,
+    clues: Vec<Vec<u8>>,
+    notes: Vec<u8>,
+}
+
+impl PuzStrings {
+    fn checksum(&self, mut checksum: u16) -> u16 {
+        for string in [&self.title, &self.author, &self.copyright] {
+            if !string.is_empty() {
+                checksum = checksum_region(string, checksum);
+                checksum = checksum_region(&[0], checksum);
+            }
+        }
+
+        for clue in &self.clues {
+            checksum = checksum_region(clue, checksum);
+        }
+
+        if !self.notes.is_empty() {
+            checksum = checksum_region(&self.notes, checksum);
+            checksum = checksum_region(&[0], checksum);
+        }
+
+        checksum
+    }
+}
+
+fn parse(bytes: &[u8]) -> Result<(TggFile, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+
+    // Some files are prefixed with extra data, the magic string is always at offset 2
+    let start = match bytes
+        .windows(MAGIC.len())
+        .position(|window| window == MAGIC)
+    {
+        Some(position) if position >= 2 => position - 2,
+        _ => return Err(Error::InvalidPuzMagic),
+    };
+    let bytes = &bytes[start..];
+
+    if bytes.len() < HEADER_LENGTH {
+        return Err(Error::UnexpectedEndOfFile);
+    }
+
+    let width = bytes[0x2C];
+    let height = bytes[0x2D];
+    let clue_count = u16::from_le_bytes([bytes[0x2E], bytes[0x2F]]);
+    let puzzle_type = u16::from_le_bytes([bytes[0x30], bytes[0x31]]);
+    let scrambled = u16::from_le_bytes([bytes[0x32], bytes[0x33]]);
+
+    if width == 0 || height == 0 {
+        return Err(Error::WidthOrHeightIsZero);
+    }
+
+    if scrambled != 0 {
+        return Err(Error::PuzSolutionScrambled);
+    }
+
+    let size = width as usize * height as usize;
+    if bytes.len() < HEADER_LENGTH + size * 2 {
+        return Err(Error::UnexpectedEndOfFile);
+    }
+
+    let solution = &bytes[HEADER_LENGTH..HEADER_LENGTH + size];
+    let player_grid = &bytes[HEADER_LENGTH + size..HEADER_LENGTH + size * 2];
+    let mut offset = HEADER_LENGTH + size * 2;
+
+    let title = read_string(bytes, &mut offset)?;
+    let author = read_string(bytes, &mut offset)?;
+    let copyright = read_string(bytes, &mut offset)?;
+    let mut clues = Vec::new();
+    for _ in 0..clue_count {
+        clues.push(read_string(bytes, &mut offset)?);
+    }
+    // Notes were only added in version 1.3 and are missing from some older files
+    let notes = if offset < bytes.len() {
+        read_string(bytes, &mut offset)?
+    } else {
+        Vec::new()
+    };
+
+    let strings = PuzStrings {
+        title,
+        author,
+        copyright,
+        clues,
+        notes,
+    };
+
+    verify_checksums(bytes, solution, player_grid, &strings, &mut warnings);
+
+    let mut rebus_grid: Option<Vec<u8>> = None;
+    let mut rebus_table: Vec<(u8, String)> = Vec::new();
+    let mut markup: Vec<u8> = Vec::new();
+
+    // Extension sections: a 4 byte name, length, checksum, data and a null terminator
+    while offset + 8 <= bytes.len() {
+        let name: String = bytes[offset..offset + 4]
+            .iter()
+            .map(|&byte| byte as char)
+            .collect();
+        let length = u16::from_le_bytes([bytes[offset + 4], bytes[offset + 5]]) as usize;
+        let checksum = u16::from_le_bytes([bytes[offset + 6], bytes[offset + 7]]);
+        offset += 8;
+
+        if offset + length > bytes.len() {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+        let data = &bytes[offset..offset + length];
+        offset += length + 1;
+
+        if checksum_region(data, 0) != checksum {
+            warnings.push(Warning::PuzChecksumMismatch {
+                section: name.clone(),
+            });
+        }
+
+        match name.as_str() {
+            "GRBS" => rebus_grid = Some(data.to_vec()),
+            "RTBL" => rebus_table = parse_rebus_table(data),
+            "GEXT" => markup = data.to_vec(),
+            _ => warnings.push(Warning::PuzExtensionIgnored { name }),
+        }
+    }
+
+    let mut grid: Vec<Vec<CrosswordBox>> = Vec::new();
+    for row in 0..height as usize {
+        let mut cells = Vec::new();
+        for column in 0..width as usize {
+            let index = row * width as usize + column;
+            let byte = solution[index];
+            // Rebus keys are stored one higher, 0 means no rebus
+            let rebus_key = rebus_grid
+                .as_ref()
+                .and_then(|rebus_grid| rebus_grid.get(index))
+                .and_then(|key| key.checked_sub(1));
+
+            let value = match (rebus_key, byte) {
+                (Some(key), _) => match rebus_table.iter().find(|(other, _)| *other == key) {
+                    Some((_, rebus)) if validate_rebus(rebus).is_ok() => {
+                        CrosswordBoxValue::Rebus(rebus.clone())
+                    }
+                    _ => {
+                        warnings.push(Warning::PuzRebusIgnored {
+                            row: row as u8,
+                            column: column as u8,
+                        });
+                        CrosswordBoxValue::Empty
+                    }
+                },
+                (None, BLOCK | DIAGRAMLESS_BLOCK) => CrosswordBoxValue::Solid,
+                (None, b) if b.is_ascii_alphabetic() => {
+                    CrosswordBoxValue::Letter(b.to_ascii_uppercase() as char)
+                }
+                (None, _) => {
+                    warnings.push(Warning::UnsupportedCharacter {
+                        row: row as u8,
+                        column: column as u8,
+                        found: byte as char,
+                    });
+                    CrosswordBoxValue::Empty
+                }
+            };
+            let style = CellStyle {
+                circled: markup.get(index).is_some_and(|byte| byte & CIRCLED != 0),
+                ..CellStyle::default()
+            };
+            cells.push(CrosswordBox::new(0, value)?.with_style(style));
+        }
+        grid.push(cells);
+    }
+
+    let numbers = standard_numbering(&grid)?;
+    let mut horizontal_clues = Vec::new();
+    let mut vertical_clues = Vec::new();
+    let mut clues = strings.clues.iter();
+
+    for row in 0..height as usize {
+        for column in 0..width as usize {
+            let number = numbers[row][column];
+            grid[row][column].number = number;
+
+            if starts_horizontal_entry(&grid, row, column) {
+                match clues.next() {
+                    Some(clue) => horizontal_clues.push(CrosswordClue::new(number, &decode(clue))),
+                    None => return Err(clue_count_mismatch(&grid, clue_count)),
+                }
+            }
+
+            if starts_vertical_entry(&grid, row, column) {
+                match clues.next() {
+                    Some(clue) => vertical_clues.push(CrosswordClue::new(number, &decode(clue))),
+                    None => return Err(clue_count_mismatch(&grid, clue_count)),
+                }
+            }
+        }
+    }
+
+    if clues.next().is_some() {
+        return Err(clue_count_mismatch(&grid, clue_count));
+    }
+
+    if horizontal_clues.len() + vertical_clues.len() > u8::MAX as usize {
+        return Err(Error::TooManyEntries);
+    }
+
+    let mut crossword = CrosswordData::new(width, height, horizontal_clues, vertical_clues, grid)?;
+    crossword.diagramless = puzzle_type == DIAGRAMLESS_PUZZLE;
+
+    for entry in crossword.entries() {
+        let clues = match entry.direction {
+            Direction::Across => &mut crossword.horizontal_clues,
+            Direction::Down => &mut crossword.vertical_clues,
+        };
+        for clue in clues.iter_mut().filter(|clue| clue.number == entry.number) {
+            let parsed = CrosswordClue::from_full_text(clue.number, &clue.value);
+            let fits = parsed
+                .enumeration
+                .as_ref()
+                .is_some_and(|enumeration| enumeration.length() == entry.answer.chars().count());
+            if fits {
+                *clue = parsed;
+            }
+        }
+    }
+
+    let description = if strings.notes.is_empty() {
+        decode(&strings.copyright)
+    } else {
+        if !strings.copyright.is_empty() {
+            warnings.push(Warning::PuzCopyrightIgnored);
+        }
+        decode(&strings.notes)
+    };
+
+    let title = imported_metadata(decode(&strings.title), "title", &mut warnings);
+    let description = imported_metadata(description, "description", &mut warnings);
+    let author = imported_metadata(decode(&strings.author), "author", &mut warnings);
+
+    let tgg_file = TggFile::assemble(
+        &title,
+        &description,
+        &author,
+        current_timestamp(),
+        GameData::Crossword(crossword),
+        Vec::new(),
+    );
+
+    Ok((tgg_file, warnings))
+}
+
+fn verify_checksums(
+    bytes: &[u8],
+    solution: &[u8],
+    player_grid: &[u8],
+    strings: &PuzStrings,
+    warnings: &mut Vec<Warning>,
+) {
+    let cib_checksum = checksum_region(&bytes[0x2C..HEADER_LENGTH], 0);
+    if cib_checksum != u16::from_le_bytes([bytes[0x0E], bytes[0x0F]]) {
+        warnings.push(Warning::PuzChecksumMismatch {
+            section: "CIB".to_string(),
+        });
+    }
+
+    let file_checksum = file_checksum(cib_checksum, solution, player_grid, strings);
+    if file_checksum != u16::from_le_bytes([bytes[0x00], bytes[0x01]]) {
+        warnings.push(Warning::PuzChecksumMismatch {
+            section: "file".to_string(),
+        });
+    }
+
+    let masked = masked_checksums(cib_checksum, solution, player_grid, strings);
+    if masked != bytes[0x10..0x18] {
+        warnings.push(Warning::PuzChecksumMismatch {
+            section: "masked".to_string(),
+        });
+    }
+}
+
+fn write(tgg_file: &TggFile, crossword: &CrosswordData) -> Result<(Vec<u8>, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+    let grid = &crossword.crossword_data;
+    crossword.validate_numbering()?;
+    // Entries are derived from the blocks alone, so bars would change the clue order
+    if grid.iter().flatten().any(|cell| cell.bars.any()) {
+        return Err(Error::PuzBarsUnsupported);
+    }
+
+    let mut solution = Vec::new();
+    let mut player_grid = Vec::new();
+    let mut clues = Vec::new();
+    let mut used_horizontal_clues = Vec::new();
+    let mut used_vertical_clues = Vec::new();
+    let mut rebus_grid = Vec::new();
+    let mut rebus_table: Vec<&str> = Vec::new();
+    let mut markup = Vec::new();
+    // Diagramless solvers start from an empty grid and find the blocks themselves
+    let empty_block = if crossword.diagramless { BLANK } else { BLOCK };
+
+    for (row, cells) in grid.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            let expected = cell.number;
+
+            let mut rebus_key = 0;
+            match &cell.value {
+                CrosswordBoxValue::Solid => {
+                    solution.push(BLOCK);
+                    player_grid.push(empty_block);
+                }
+                // Void boxes end entries like blocks, so the clues stay in order
+                CrosswordBoxValue::Void => {
+                    warnings.push(Warning::PuzVoidCellIgnored {
+                        row: row as u8,
+                        column: column as u8,
+                    });
+                    solution.push(BLOCK);
+                    player_grid.push(empty_block);
+                }
+                CrosswordBoxValue::Letter(letter) => {
+                    solution.push(*letter as u8);
+                    player_grid.push(BLANK);
+                }
+                // The solution grid holds the first character, the rest is in the rebus table
+                CrosswordBoxValue::Rebus(rebus) => {
+                    let key = match rebus_table.iter().position(|other| other == rebus) {
+                        Some(key) => key,
+                        // Keys are stored one byte above their index, zero means no rebus
+                        None if rebus_table.len() == 255 => return Err(Error::PuzTooManyRebuses),
+                        None => {
+                            rebus_table.push(rebus);
+                            rebus_table.len() - 1
+                        }
+                    };
+                    rebus_key = key as u8 + 1;
+                    solution.push(rebus.as_bytes()[0]);
+                    player_grid.push(BLANK);
+                }
+                CrosswordBoxValue::Empty => {
+                    return Err(Error::EmptyBoxInSolution {
+                        row: row as u8,
+                        column: column as u8,
+                    })
+                }
+            }
+            rebus_grid.push(rebus_key);
+
+            // Only circles can be stored, the other markup flags are for the solver
+            markup.push(if cell.style.circled { CIRCLED } else { 0 });
+            if cell.style.shade.is_some() || cell.style.bold_border {
+                warnings.push(Warning::PuzStyleIgnored {
+                    row: row as u8,
+                    column: column as u8,
+                });
+            }
+
+            if starts_horizontal_entry(grid, row, column) {
+                match crossword
+                    .horizontal_clues
+                    .iter()
+                    .find(|clue| clue.number == expected)
+                {
+                    Some(clue) => clues.push(clue.full_text()),
+                    None => return Err(Error::MissingHorizontalClue { number: expected }),
+                }
+                used_horizontal_clues.push(expected);
+            }
+
+            if starts_vertical_entry(grid, row, column) {
+                match crossword
+                    .vertical_clues
+                    .iter()
+                    .find(|clue| clue.number == expected)
+                {
+                    Some(clue) => clues.push(clue.full_text()),
+                    None => return Err(Error::MissingVerticalClue { number: expected }),
+                }
+                used_vertical_clues.push(expected);
+            }
+        }
+    }
+
+    for clue in &crossword.horizontal_clues {
+        if !used_horizontal_clues.contains(&clue.number) {
+            warnings.push(Warning::PuzClueIgnored {
+                number: clue.number,
+            });
+        }
+    }
+
+    for clue in &crossword.vertical_clues {
+        if !used_vertical_clues.contains(&clue.number) {
+            warnings.push(Warning::PuzClueIgnored {
+                number: clue.number,
+            });
+        }
+    }
+
+    let mut lossy = false;
+    let strings = PuzStrings {
+        title: encode(&tgg_file.metadata.title, &mut lossy),
+        author: encode(&tgg_file.metadata.author, &mut lossy),
+        copyright: Vec::new(),
+        clues: clues.iter().map(|clue| encode(clue, &mut lossy)).collect(),
+        notes: encode(&tgg_file.metadata.description, &mut lossy),
+    };
+    if lossy {
+        warnings.push(Warning::NonLatin1Text);
+    }
+
+    let mut bytes = vec![0; HEADER_LENGTH];
+    bytes[0x02..0x0E].copy_from_slice(MAGIC);
+    bytes[0x18..0x1C].copy_from_slice(VERSION);
+    bytes[0x2C] = crossword.width;
+    bytes[0x2D] = crossword.height;
+    bytes[0x2E..0x30].copy_from_slice(&(strings.clues.len() as u16).to_le_bytes());
+    let puzzle_type = if crossword.diagramless {
+        DIAGRAMLESS_PUZZLE
+    } else {
+        NORMAL_PUZZLE
+    };
+    bytes[0x30..0x32].copy_from_slice(&puzzle_type.to_le_bytes());
+
+    let cib_checksum = checksum_region(&bytes[0x2C..HEADER_LENGTH], 0);
+    let file_checksum = file_checksum(cib_checksum, &solution, &player_grid, &strings);
+    let masked = masked_checksums(cib_checksum, &solution, &player_grid, &strings);
+
+    bytes[0x00..0x02].copy_from_slice(&file_checksum.to_le_bytes());
+    bytes[0x0E..0x10].copy_from_slice(&cib_checksum.to_le_bytes());
+    bytes[0x10..0x18].copy_from_slice(&masked);
+
+    bytes.extend(&solution);
+    bytes.extend(&player_grid);
+    for string in [&strings.title, &strings.author, &strings.copyright] {
+        bytes.extend(string);
+        bytes.push(0);
+    }
+    for clue in &strings.clues {
+        bytes.extend(clue);
+        bytes.push(0);
+    }
+    bytes.extend(&strings.notes);
+    bytes.push(0);
+
+    if !rebus_table.is_empty() {
+        let table: String = rebus_table
+            .iter()
+            .enumerate()
+            .map(|(key, rebus)| format!("{:>2}:{};", key, rebus))
+            .collect();
+        write_section(&mut bytes, b"GRBS", &rebus_grid);
+        write_section(&mut bytes, b"RTBL", table.as_bytes());
+    }
+    if markup.iter().any(|&byte| byte != 0) {
+        write_section(&mut bytes, b"GEXT", &markup);
+    }
+
+    Ok((bytes, warnings))
+}
+
+/// Writes an extension section: its name, length, checksum, data and a null terminator.
+fn write_section(bytes: &mut Vec<u8>, name: &[u8], data: &[u8]) {
+    bytes.extend(name);
+    bytes.extend((data.len() as u16).to_le_bytes());
+    bytes.extend(checksum_region(data, 0).to_le_bytes());
+    bytes.extend(data);
+    bytes.push(0);
+}
+
+/// Parses the rebus table, entries like ` 1:HEART;` that map a key to its rebus.
+fn parse_rebus_table(data: &[u8]) -> Vec<(u8, String)> {
+    decode(data)
+        .split(';')
+        .filter_map(|entry| {
+            let (key, rebus) = entry.split_once(':')?;
+            let key = key.trim().parse().ok()?;
+            Some((key, rebus.to_ascii_uppercase()))
+        })
+        .collect()
+}
+
+fn file_checksum(
+    cib_checksum: u16,
+    solution: &[u8],
+    player_grid: &[u8],
+    strings: &PuzStrings,
+) -> u16 {
+    let mut checksum = cib_checksum;
+    checksum = checksum_region(solution, checksum);
+    checksum = checksum_region(player_grid, checksum);
+    strings.checksum(checksum)
+}
+
+fn masked_checksums(
+    cib_checksum: u16,
+    solution: &[u8],
+    player_grid: &[u8],
+    strings: &PuzStrings,
+) -> [u8; 8] {
+    let checksums = [
+        cib_checksum,
+        checksum_region(solution, 0),
+        checksum_region(player_grid, 0),
+        strings.checksum(0),
+    ];
+
+    let mut masked = [0; 8];
+    for (index, checksum) in checksums.iter().enumerate() {
+        masked[index] = MASK[index] ^ (checksum & 0xFF) as u8;
+        masked[index + 4] = MASK[index + 4] ^ (checksum >> 8) as u8;
+    }
+
+    masked
+}
+
+fn checksum_region(bytes: &[u8], mut checksum: u16) -> u16 {
+    for &byte in bytes {
+        checksum = if checksum & 1 != 0 {
+            (checksum >> 1).wrapping_add(0x8000)
+        } else {
+            checksum >> 1
+        };
+        checksum = checksum.wrapping_add(byte as u16);
+    }
+
+    checksum
+}
+
+fn read_string(bytes: &[u8], offset: &mut usize) -> Result<Vec<u8>, Error> {
+    let remaining = bytes.get(*offset..).unwrap_or_default();
+    let length = match remaining.iter().position(|&byte| byte == 0) {
+        Some(length) => length,
+        None => return Err(Error::UnexpectedEndOfFile),
+    };
+
+    let string = bytes[*offset..*offset + length].to_vec();
+    *offset += length + 1;
+
+    Ok(string)
+}
+
+fn clue_count_mismatch(grid: &[Vec<CrosswordBox>], clue_count: u16) -> Error {
+    let mut entries = 0;
+    for (row, cells) in grid.iter().enumerate() {
+        for column in 0..cells.len() {
+            entries += starts_horizontal_entry(grid, row, column) as u16;
+            entries += starts_vertical_entry(grid, row, column) as u16;
+        }
+    }
+
+    Error::PuzClueCountMismatch {
+        expected: entries,
+        found: clue_count,
+    }
+}
+
+fn decode(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+fn encode(string: &str, lossy: &mut bool) -> Vec<u8> {
+    string
+        .chars()
+        .map(|c| match u8::try_from(c as u32) {
+            Ok(byte) if byte != 0 => byte,
+            _ => {
+                *lossy = true;
+                b'?'
+            }
+        })
+        .collect()
+}
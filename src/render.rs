@@ -0,0 +1,292 @@
+//! Plain text rendering of crosswords for terminals and test snapshots.
+
+use crate::crossword::{CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData};
+
+const CELL_WIDTH: usize = 3;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_REVERSE: &str = "\x1b[7m";
+
+/// Whether letters from the solution are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    Blank,
+    Solution,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    BoxDrawing,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextRenderOptions {
+    pub mode: RenderMode,
+    pub charset: Charset,
+    /// Shows box numbers in the top left corner of their box.
+    pub numbers: bool,
+    /// Uses ANSI escape codes to highlight solid and shaded boxes, numbers and letters.
+    pub color: bool,
+    /// Lists the clues underneath the grid.
+    pub clues: bool,
+}
+
+impl Default for TextRenderOptions {
+    fn default() -> TextRenderOptions {
+        TextRenderOptions {
+            mode: RenderMode::Solution,
+            charset: Charset::BoxDrawing,
+            numbers: true,
+            color: false,
+            clues: true,
+        }
+    }
+}
+
+struct Glyphs {
+    horizontal: char,
+    vertical: char,
+    solid: char,
+    // Bars between boxes
+    bar_horizontal: char,
+    bar_vertical: char,
+    // Marks the end of a rebus cut off to fit its box
+    ellipsis: char,
+    // Corners and junctions for the top, middle and bottom border lines
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+impl Charset {
+    fn glyphs(&self) -> Glyphs {
+        match self {
+            Charset::Ascii => Glyphs {
+                horizontal: '-',
+                vertical: '|',
+                solid: '#',
+                bar_horizontal: '=',
+                bar_vertical: '#',
+                ellipsis: '~',
+                top: ['+', '+', '+'],
+                middle: ['+', '+', '+'],
+                bottom: ['+', '+', '+'],
+            },
+            Charset::BoxDrawing => Glyphs {
+                horizontal: '─',
+                vertical: '│',
+                solid: '█',
+                bar_horizontal: '━',
+                bar_vertical: '┃',
+                ellipsis: '…',
+                top: ['┌', '┬', '┐'],
+                middle: ['├', '┼', '┤'],
+                bottom: ['└', '┴', '┘'],
+            },
+        }
+    }
+}
+
+impl CrosswordData {
+    pub fn render_text(&self, options: &TextRenderOptions) -> String {
+        // Diagramless solvers only get the dimensions
+        if self.diagramless && options.mode == RenderMode::Blank {
+            return self.without_diagram().render_text(options);
+        }
+
+        let glyphs = options.charset.glyphs();
+        let mut output = String::new();
+
+        for (row, cells) in self.crossword_data.iter().enumerate() {
+            let (above, junctions) = match row {
+                0 => (None, glyphs.top),
+                _ => (Some(&self.crossword_data[row - 1][..]), glyphs.middle),
+            };
+            output.push_str(&border_line(above, Some(cells), &glyphs, junctions));
+
+            if options.numbers {
+                output.push_str(&cell_line(cells, &glyphs, options, number_content));
+            }
+            output.push_str(&cell_line(cells, &glyphs, options, value_content));
+        }
+        output.push_str(&border_line(
+            self.crossword_data.last().map(|cells| &cells[..]),
+            None,
+            &glyphs,
+            glyphs.bottom,
+        ));
+
+        if options.clues {
+            output.push_str(&clue_list("Across", &self.horizontal_clues));
+            output.push_str(&clue_list("Down", &self.vertical_clues));
+        }
+
+        output
+    }
+}
+
+/// A horizontal border between two rows, drawing the bottom bars of the boxes `above`
+/// it. Borders are only drawn next to boxes that aren't void.
+fn border_line(
+    above: Option<&[CrosswordBox]>,
+    below: Option<&[CrosswordBox]>,
+    glyphs: &Glyphs,
+    junctions: [char; 3],
+) -> String {
+    let width = above.or(below).map_or(0, |cells| cells.len());
+    let drawn = |column: usize| {
+        [above, below]
+            .iter()
+            .flatten()
+            .any(|cells| !is_void(&cells[column]))
+    };
+    let mut line = String::new();
+
+    for column in 0..width {
+        let junction = if column == 0 {
+            junctions[0]
+        } else {
+            junctions[1]
+        };
+        let joined = drawn(column) || (column > 0 && drawn(column - 1));
+        line.push(if joined { junction } else { ' ' });
+
+        let barred = above.is_some_and(|cells| cells[column].bars.bottom);
+        let glyph = match (drawn(column), barred) {
+            (false, _) => ' ',
+            (true, true) => glyphs.bar_horizontal,
+            (true, false) => glyphs.horizontal,
+        };
+        line.extend(std::iter::repeat(glyph).take(CELL_WIDTH));
+    }
+    line.push(if width > 0 && drawn(width - 1) {
+        junctions[2]
+    } else {
+        ' '
+    });
+
+    let mut line = line.trim_end().to_string();
+    line.push('\n');
+
+    line
+}
+
+/// Content of a box line, the text and the ANSI style used when colors are enabled.
+type Content = (String, &'static str);
+
+fn cell_line(
+    cells: &[CrosswordBox],
+    glyphs: &Glyphs,
+    options: &TextRenderOptions,
+    content: fn(&CrosswordBox, &TextRenderOptions) -> Content,
+) -> String {
+    let mut line = String::new();
+
+    // The line left of a box, drawn unless the box and the one before it are void
+    let separator = |index: usize| {
+        let before = index.checked_sub(1).map(|index| &cells[index]);
+        match (before, cells.get(index)) {
+            (Some(before), _) if before.bars.right => glyphs.bar_vertical,
+            (before, after) if before.into_iter().chain(after).any(|cell| !is_void(cell)) => {
+                glyphs.vertical
+            }
+            _ => ' ',
+        }
+    };
+
+    for (index, cell) in cells.iter().enumerate() {
+        line.push(separator(index));
+        if is_void(cell) {
+            line.push_str(&" ".repeat(CELL_WIDTH));
+        } else if cell.value == CrosswordBoxValue::Solid {
+            if options.color {
+                line.push_str(ANSI_REVERSE);
+                line.push_str(&" ".repeat(CELL_WIDTH));
+                line.push_str(ANSI_RESET);
+            } else {
+                line.extend(std::iter::repeat(glyphs.solid).take(CELL_WIDTH));
+            }
+        } else {
+            let (text, style) = content(cell, options);
+            let padded = format!("{:<width$}", text, width = CELL_WIDTH);
+            if let (true, Some([red, green, blue])) = (options.color, cell.style.shade) {
+                line.push_str(&format!("\x1b[48;2;{};{};{}m", red, green, blue));
+                line.push_str(style);
+                line.push_str(&padded);
+                line.push_str(ANSI_RESET);
+            } else if options.color && !text.trim().is_empty() {
+                line.push_str(style);
+                line.push_str(&padded);
+                line.push_str(ANSI_RESET);
+            } else {
+                line.push_str(&padded);
+            }
+        }
+    }
+    line.push(separator(cells.len()));
+
+    let mut line = line.trim_end().to_string();
+    line.push('\n');
+
+    line
+}
+
+fn is_void(cell: &CrosswordBox) -> bool {
+    cell.value == CrosswordBoxValue::Void
+}
+
+fn number_content(cell: &CrosswordBox, _: &TextRenderOptions) -> Content {
+    if cell.number == 0 {
+        (String::new(), ANSI_DIM)
+    } else {
+        (cell.number.to_string(), ANSI_DIM)
+    }
+}
+
+fn value_content(cell: &CrosswordBox, options: &TextRenderOptions) -> Content {
+    match (&cell.value, options.mode) {
+        // Circled boxes put their letter in parentheses
+        (CrosswordBoxValue::Letter(letter), RenderMode::Solution) if cell.style.circled => {
+            (format!("({})", letter), ANSI_BOLD)
+        }
+        (CrosswordBoxValue::Letter(letter), RenderMode::Solution) => {
+            (format!(" {}", letter), ANSI_BOLD)
+        }
+        // Rebuses that don't fit are cut off with an ellipsis
+        (CrosswordBoxValue::Rebus(rebus), RenderMode::Solution) => {
+            let text = if rebus.len() > CELL_WIDTH {
+                let ellipsis = options.charset.glyphs().ellipsis;
+                format!("{}{}", &rebus[..CELL_WIDTH - 1], ellipsis)
+            } else {
+                rebus.clone()
+            };
+            (text, ANSI_BOLD)
+        }
+        _ if cell.style.circled => ("( )".to_string(), ANSI_BOLD),
+        _ => (String::new(), ANSI_BOLD),
+    }
+}
+
+fn clue_list(title: &str, clues: &[CrosswordClue]) -> String {
+    let mut list = format!("\n{}\n", title);
+
+    let number_width = clues
+        .iter()
+        .map(|clue| clue.number.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    for clue in clues {
+        list.push_str(&format!(
+            "{:>width$}. {}\n",
+            clue.number,
+            clue.full_text(),
+            width = number_width
+        ));
+    }
+
+    list
+}
@@ -0,0 +1,108 @@
+//! Solves crosswords from their grid shape and a word list alone, to check whether a
+//! puzzle has a unique solution before it's published.
+//!
+//! The letters are cleared and the grid is filled every way the word list allows, with
+//! the same search as autofill. Like autofill, no word is used twice in one solution.
+
+use crate::autofill::AutofillOptions;
+use crate::crossword::{CrosswordBoxValue, CrosswordData};
+use crate::word_list::WordList;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct SolverOptions {
+    /// Stops after placing this many words, `None` for no limit.
+    pub max_steps: Option<u64>,
+    pub time_limit: Option<Duration>,
+    /// Stops after finding this many solutions.
+    pub max_solutions: usize,
+}
+
+impl Default for SolverOptions {
+    fn default() -> SolverOptions {
+        SolverOptions {
+            max_steps: Some(1_000_000),
+            time_limit: None,
+            max_solutions: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveResult {
+    /// Every solution found, highest scoring first.
+    pub solutions: Vec<CrosswordData>,
+    /// Index of the puzzle's own solution in `solutions`, if the word list allows it.
+    pub intended: Option<usize>,
+    /// Row and column of every box whose letter differs between solutions.
+    pub ambiguous_boxes: Vec<(u8, u8)>,
+    /// Whether the search ran out of steps, time or solutions before it was done, in
+    /// which case solutions may have been missed.
+    pub stopped: bool,
+}
+
+impl SolveResult {
+    /// Whether the puzzle's own solution is the only one the word list allows.
+    pub fn is_unique(&self) -> bool {
+        !self.stopped && self.solutions.len() == 1 && self.intended == Some(0)
+    }
+}
+
+impl CrosswordData {
+    /// Solves the puzzle using only its grid shape and the word list. The letters in the
+    /// puzzle are only used to find the intended solution among the results.
+    pub fn solve(&self, words: &WordList, options: &SolverOptions) -> SolveResult {
+        let mut blank = self.clone();
+        for crossword_box in blank.crossword_data.iter_mut().flatten() {
+            if let CrosswordBoxValue::Letter(_) = crossword_box.value {
+                crossword_box.value = CrosswordBoxValue::Empty;
+            }
+        }
+
+        // One more than wanted, so hitting the limit can be told apart from finishing
+        let autofill_options = AutofillOptions {
+            max_steps: options.max_steps,
+            time_limit: options.time_limit,
+            results: options.max_solutions + 1,
+            ..AutofillOptions::default()
+        };
+        let result = blank.fill(words, &autofill_options, true);
+
+        let mut stopped = result.stopped;
+        let mut solutions: Vec<CrosswordData> = result
+            .fills
+            .into_iter()
+            .map(|fill| fill.crossword)
+            .collect();
+        if solutions.len() > options.max_solutions {
+            solutions.truncate(options.max_solutions);
+            stopped = true;
+        }
+
+        let intended = solutions
+            .iter()
+            .position(|solution| solution.crossword_data == self.crossword_data);
+
+        let mut ambiguous_boxes = Vec::new();
+        if let Some(first) = solutions.first() {
+            for row in 0..self.height as usize {
+                for column in 0..self.width as usize {
+                    let value = &first.crossword_data[row][column].value;
+                    if solutions
+                        .iter()
+                        .any(|solution| solution.crossword_data[row][column].value != *value)
+                    {
+                        ambiguous_boxes.push((row as u8, column as u8));
+                    }
+                }
+            }
+        }
+
+        SolveResult {
+            solutions,
+            intended,
+            ambiguous_boxes,
+            stopped,
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! SVG rendering of crossword grids for print and web.
+
+use crate::crossword::{CrosswordBoxValue, CrosswordClue, CrosswordData};
+use crate::render::RenderMode;
+use crate::utils::{hex_color, number};
+use crate::xml::Element;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+/// Rough average glyph width relative to the font size, used to wrap clue text.
+const AVERAGE_GLYPH_WIDTH: f32 = 0.55;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberPlacement {
+    TopLeft,
+    TopRight,
+}
+
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    pub mode: RenderMode,
+    pub cell_size: f32,
+    /// Space around the grid and between the grid and the clues.
+    pub padding: f32,
+    pub font_family: String,
+    pub letter_font_size: f32,
+    pub number_font_size: f32,
+    pub number_placement: NumberPlacement,
+    pub solid_fill: String,
+    pub line_color: String,
+    /// Embeds the across and down clue lists in two columns underneath the grid.
+    pub clues: bool,
+    pub clue_font_size: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> SvgOptions {
+        SvgOptions {
+            mode: RenderMode::Blank,
+            cell_size: 32.0,
+            padding: 16.0,
+            font_family: "Helvetica, Arial, sans-serif".to_string(),
+            letter_font_size: 20.0,
+            number_font_size: 9.0,
+            number_placement: NumberPlacement::TopLeft,
+            solid_fill: "#000000".to_string(),
+            line_color: "#000000".to_string(),
+            clues: false,
+            clue_font_size: 12.0,
+        }
+    }
+}
+
+impl CrosswordData {
+    pub fn render_svg(&self, options: &SvgOptions) -> String {
+        // Diagramless solvers only get the dimensions
+        if self.diagramless && options.mode == RenderMode::Blank {
+            return self.without_diagram().render_svg(options);
+        }
+
+        let cell = options.cell_size;
+        let grid_width = self.width as f32 * cell;
+        let grid_height = self.height as f32 * cell;
+
+        let mut cells = Element::new("g").with_attribute("stroke", &options.line_color);
+        let mut shapes = Element::new("g")
+            .with_attribute("fill", "none")
+            .with_attribute("stroke", &options.line_color);
+        let mut bars = Element::new("g")
+            .with_attribute("stroke", &options.line_color)
+            .with_attribute("stroke-width", &number(cell * 0.1))
+            .with_attribute("stroke-linecap", "square");
+        let mut numbers = Element::new("g")
+            .with_attribute("font-size", &number(options.number_font_size))
+            .with_attribute(
+                "text-anchor",
+                match options.number_placement {
+                    NumberPlacement::TopLeft => "start",
+                    NumberPlacement::TopRight => "end",
+                },
+            );
+        let mut letters = Element::new("g")
+            .with_attribute("font-size", &number(options.letter_font_size))
+            .with_attribute("text-anchor", "middle");
+
+        for (row, boxes) in self.crossword_data.iter().enumerate() {
+            for (column, crossword_box) in boxes.iter().enumerate() {
+                let x = options.padding + column as f32 * cell;
+                let y = options.padding + row as f32 * cell;
+
+                // Void boxes aren't part of the puzzle, so nothing is drawn for them
+                if crossword_box.value == CrosswordBoxValue::Void {
+                    continue;
+                }
+
+                let style = &crossword_box.style;
+                let fill = match (&crossword_box.value, style.shade) {
+                    (CrosswordBoxValue::Solid, _) => options.solid_fill.clone(),
+                    (_, Some(shade)) => hex_color(shade),
+                    _ => "#ffffff".to_string(),
+                };
+                cells = cells.with_child(
+                    Element::new("rect")
+                        .with_attribute("x", &number(x))
+                        .with_attribute("y", &number(y))
+                        .with_attribute("width", &number(cell))
+                        .with_attribute("height", &number(cell))
+                        .with_attribute("fill", &fill),
+                );
+
+                if crossword_box.bars.right {
+                    bars = bars.with_child(line(x + cell, y, x + cell, y + cell));
+                }
+                if crossword_box.bars.bottom {
+                    bars = bars.with_child(line(x, y + cell, x + cell, y + cell));
+                }
+
+                if crossword_box.value == CrosswordBoxValue::Solid {
+                    continue;
+                }
+
+                if style.circled {
+                    shapes = shapes.with_child(
+                        Element::new("circle")
+                            .with_attribute("cx", &number(x + cell / 2.0))
+                            .with_attribute("cy", &number(y + cell / 2.0))
+                            .with_attribute("r", &number(cell * 0.45)),
+                    );
+                }
+                if style.bold_border {
+                    let inset = cell * 0.04;
+                    shapes = shapes.with_child(
+                        Element::new("rect")
+                            .with_attribute("x", &number(x + inset))
+                            .with_attribute("y", &number(y + inset))
+                            .with_attribute("width", &number(cell - inset * 2.0))
+                            .with_attribute("height", &number(cell - inset * 2.0))
+                            .with_attribute("stroke-width", &number(cell * 0.08)),
+                    );
+                }
+
+                if crossword_box.number != 0 {
+                    let inset = cell * 0.08;
+                    let number_x = match options.number_placement {
+                        NumberPlacement::TopLeft => x + inset,
+                        NumberPlacement::TopRight => x + cell - inset,
+                    };
+                    numbers = numbers.with_child(
+                        Element::new("text")
+                            .with_attribute("x", &number(number_x))
+                            .with_attribute("y", &number(y + inset + options.number_font_size))
+                            .with_text(&crossword_box.number.to_string()),
+                    );
+                }
+
+                if let (Some(content), RenderMode::Solution) =
+                    (crossword_box.value.content(), options.mode)
+                {
+                    // Letters sit a little below the center to leave room for the number
+                    let baseline = y + cell * 0.55 + options.letter_font_size * 0.4;
+                    let mut text = Element::new("text")
+                        .with_attribute("x", &number(x + cell / 2.0))
+                        .with_attribute("y", &number(baseline));
+                    // Rebuses shrink to fit the box
+                    if content.len() > 1 {
+                        let font_size =
+                            (cell * 1.4 / content.len() as f32).min(options.letter_font_size);
+                        text = text.with_attribute("font-size", &number(font_size));
+                    }
+                    letters = letters.with_child(text.with_text(&content));
+                }
+            }
+        }
+
+        let border = Element::new("rect")
+            .with_attribute("x", &number(options.padding))
+            .with_attribute("y", &number(options.padding))
+            .with_attribute("width", &number(grid_width))
+            .with_attribute("height", &number(grid_height))
+            .with_attribute("fill", "none")
+            .with_attribute("stroke", &options.line_color)
+            .with_attribute("stroke-width", "2");
+
+        let mut width = grid_width + options.padding * 2.0;
+        let mut height = grid_height + options.padding * 2.0;
+
+        let mut svg = Element::new("svg")
+            .with_attribute("xmlns", SVG_NAMESPACE)
+            .with_attribute("font-family", &options.font_family);
+        let mut content = vec![cells, shapes, bars];
+        // A shaped grid is outlined by its boxes, a rectangle around it would show the voids
+        if !self.is_shaped() {
+            content.push(border);
+        }
+        content.extend([numbers, letters]);
+
+        if options.clues {
+            let top = height;
+            // Narrow grids still get columns wide enough for a few words per line
+            let column_width = (grid_width / 2.0).max(options.clue_font_size * 16.0);
+            let (across, across_height) = clue_column(
+                "Across",
+                &self.horizontal_clues,
+                options.padding,
+                top,
+                column_width,
+                options,
+            );
+            let (down, down_height) = clue_column(
+                "Down",
+                &self.vertical_clues,
+                options.padding * 2.0 + column_width,
+                top,
+                column_width,
+                options,
+            );
+
+            width = width.max(options.padding * 3.0 + column_width * 2.0);
+            height = top + across_height.max(down_height) + options.padding;
+            content.push(across);
+            content.push(down);
+        }
+
+        svg = svg
+            .with_attribute("width", &number(width))
+            .with_attribute("height", &number(height))
+            .with_attribute(
+                "viewBox",
+                &format!("0 0 {} {}", number(width), number(height)),
+            );
+        for element in content {
+            svg = svg.with_child(element);
+        }
+
+        svg.to_document()
+    }
+}
+
+/// Lays out a titled clue list, returning the group and its height.
+fn clue_column(
+    title: &str,
+    clues: &[CrosswordClue],
+    x: f32,
+    top: f32,
+    width: f32,
+    options: &SvgOptions,
+) -> (Element, f32) {
+    let line_height = options.clue_font_size * 1.3;
+    let mut y = top + options.clue_font_size;
+
+    let mut group = Element::new("g")
+        .with_attribute("font-size", &number(options.clue_font_size))
+        .with_child(
+            Element::new("text")
+                .with_attribute("x", &number(x))
+                .with_attribute("y", &number(y))
+                .with_attribute("font-weight", "bold")
+                .with_text(title),
+        );
+
+    let indent = options.clue_font_size * 2.0;
+    let max_chars = ((width - indent) / (options.clue_font_size * AVERAGE_GLYPH_WIDTH)) as usize;
+
+    for clue in clues {
+        y += line_height;
+        group = group.with_child(
+            Element::new("text")
+                .with_attribute("x", &number(x))
+                .with_attribute("y", &number(y))
+                .with_attribute("font-weight", "bold")
+                .with_text(&clue.number.to_string()),
+        );
+
+        for (index, line) in wrap(&clue.full_text(), max_chars.max(1)).iter().enumerate() {
+            if index > 0 {
+                y += line_height;
+            }
+            group = group.with_child(
+                Element::new("text")
+                    .with_attribute("x", &number(x + indent))
+                    .with_attribute("y", &number(y))
+                    .with_text(line),
+            );
+        }
+    }
+
+    (group, y - top + line_height)
+}
+
+/// Greedily wraps text on spaces so no line is longer than `max_chars`, unless a single
+/// word is longer than that.
+pub(crate) fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn line(x1: f32, y1: f32, x2: f32, y2: f32) -> Element {
+    Element::new("line")
+        .with_attribute("x1", &number(x1))
+        .with_attribute("y1", &number(y1))
+        .with_attribute("x2", &number(x2))
+        .with_attribute("y2", &number(y2))
+}
@@ -0,0 +1,729 @@
+//! Plain-text crossword format, meant to be written by hand in a text editor.
+//!
+//! ```text
+//! Title: Test Crossword
+//! Description: Just doing some testing
+//! Author: Maksim Straus
+//! Created: 1365861497
+//!
+//! Grid:
+//! #  1M 2A 3N #
+//! 4F A  C  E  S
+//!
+//! Across:
+//! 1. Wide-angle lens setting
+//!
+//! Down:
+//! 2. Unit for land measurement
+//! ```
+//!
+//! The header is a list of `Key: value` lines. `Title`, `Description` and `Author` are
+//! required, `Created` is an optional unix timestamp that defaults to the current time.
+//! Diagramless crosswords set `Diagramless: yes`.
+//!
+//! Every grid cell is a whitespace separated token made of an optional number followed
+//! by `#` for a solid box, `.` for an empty box, `~` for a void box that isn't part of
+//! the grid, an uppercase letter or a rebus in brackets, like `12[HEART]`. A `|` after
+//! the value marks a bar on the right edge of the box and a `_` a bar on the bottom edge,
+//! like `7A|_`. Styled boxes end with a comma separated list of styles in braces:
+//! `circle`, `bold` for a bold border or a `#rrggbb` shade, like `5A{circle,#ffcc00}`.
+//!
+//! Clues are written as `<number>. <text>`, optionally ending with an enumeration like
+//! `(5,3)` or `(4-4)`. Header values and clue text can contain `\n` and `\\` escapes.
+//!
+//! Word searches set the `Game: Word Search` header, their grid only contains letters and
+//! the clue sections are replaced by a `Words:` section with one word per line.
+
+use crate::crossword::{
+    validate_rebus, Bars, CellStyle, CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData,
+};
+use crate::utils::{hex_color, parse_hex_color};
+use crate::word_search::WordSearchData;
+use crate::{current_timestamp, Error, Game, GameData, TggFile};
+
+impl TggFile {
+    pub fn from_text(text: &str) -> Result<TggFile, Error> {
+        parse(text)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        if self.header.game != Game::Crossword {
+            text.push_str(&format!("Game: {}\n", self.header.game));
+        }
+        text.push_str(&format!("Title: {}\n", escape(&self.metadata.title)));
+        text.push_str(&format!(
+            "Description: {}\n",
+            escape(&self.metadata.description)
+        ));
+        text.push_str(&format!("Author: {}\n", escape(&self.metadata.author)));
+        text.push_str(&format!("Created: {}\n", self.metadata.creation_date));
+        if self
+            .get_crossword()
+            .is_some_and(|crossword| crossword.diagramless)
+        {
+            text.push_str("Diagramless: yes\n");
+        }
+
+        match &self.gamedata {
+            GameData::Crossword(crossword) => text.push_str(&crossword_to_text(crossword)),
+            GameData::WordSearch(word_search) => text.push_str(&word_search_to_text(word_search)),
+        }
+
+        text
+    }
+}
+
+fn crossword_to_text(crossword: &CrosswordData) -> String {
+    let mut text = String::new();
+
+    let tokens: Vec<Vec<String>> = crossword
+        .crossword_data
+        .iter()
+        .map(|row| row.iter().map(box_to_token).collect())
+        .collect();
+    let cell_width = tokens
+        .iter()
+        .flatten()
+        .map(|token| token.len())
+        .max()
+        .unwrap_or(1);
+
+    text.push_str("\nGrid:\n");
+    for row in &tokens {
+        let line = row
+            .iter()
+            .map(|token| format!("{:<width$}", token, width = cell_width))
+            .collect::<Vec<String>>()
+            .join(" ");
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+
+    text.push_str("\nAcross:\n");
+    for clue in &crossword.horizontal_clues {
+        text.push_str(&format!("{}. {}\n", clue.number, escape(&clue.full_text())));
+    }
+
+    text.push_str("\nDown:\n");
+    for clue in &crossword.vertical_clues {
+        text.push_str(&format!("{}. {}\n", clue.number, escape(&clue.full_text())));
+    }
+
+    text
+}
+
+fn word_search_to_text(word_search: &WordSearchData) -> String {
+    let mut text = String::new();
+
+    text.push_str("\nGrid:\n");
+    for row in &word_search.grid {
+        let line = row
+            .iter()
+            .map(|letter| letter.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        text.push_str(&line);
+        text.push('\n');
+    }
+
+    text.push_str("\nWords:\n");
+    for word in &word_search.words {
+        text.push_str(word);
+        text.push('\n');
+    }
+
+    text
+}
+
+fn box_to_token(crossword_box: &CrosswordBox) -> String {
+    let value = match &crossword_box.value {
+        CrosswordBoxValue::Empty => ".".to_string(),
+        CrosswordBoxValue::Void => "~".to_string(),
+        CrosswordBoxValue::Rebus(rebus) => format!("[{}]", rebus),
+        _ => crossword_box.value.to_string(),
+    };
+
+    let mut token = if crossword_box.number == 0 {
+        value
+    } else {
+        format!("{}{}", crossword_box.number, value)
+    };
+
+    if crossword_box.bars.right {
+        token.push('|');
+    }
+    if crossword_box.bars.bottom {
+        token.push('_');
+    }
+
+    let style = crossword_box.style;
+    if !style.is_plain() {
+        let mut styles = Vec::new();
+        if style.circled {
+            styles.push("circle".to_string());
+        }
+        if style.bold_border {
+            styles.push("bold".to_string());
+        }
+        if let Some(shade) = style.shade {
+            styles.push(hex_color(shade));
+        }
+        token.push_str(&format!("{{{}}}", styles.join(",")));
+    }
+
+    token
+}
+
+#[derive(PartialEq)]
+enum Section {
+    Header,
+    Grid,
+    Across,
+    Down,
+    Words,
+}
+
+fn parse(text: &str) -> Result<TggFile, Error> {
+    let mut title: Option<String> = None;
+    let mut description: Option<String> = None;
+    let mut author: Option<String> = None;
+    let mut creation_date: Option<u32> = None;
+    let mut diagramless = false;
+    let mut game = Game::Crossword;
+
+    let mut grid: Vec<Vec<CrosswordBox>> = Vec::new();
+    let mut letters: Vec<Vec<char>> = Vec::new();
+    let mut horizontal_clues: Vec<CrosswordClue> = Vec::new();
+    let mut vertical_clues: Vec<CrosswordClue> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    // Where everything came from, so errors found after parsing can point at a line
+    let mut locations = Locations::default();
+
+    let mut section = Section::Header;
+    let mut last_line = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index as u32 + 1;
+        last_line = line_number;
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match trimmed.to_ascii_lowercase().as_str() {
+            "grid:" => {
+                if section != Section::Header {
+                    return Err(syntax_error(line_number, 1, "grid must follow the header"));
+                }
+                section = Section::Grid;
+                continue;
+            }
+            "across:" if game == Game::Crossword => {
+                section = Section::Across;
+                continue;
+            }
+            "down:" if game == Game::Crossword => {
+                section = Section::Down;
+                continue;
+            }
+            "words:" if game == Game::WordSearch => {
+                section = Section::Words;
+                continue;
+            }
+            _ => {}
+        }
+
+        match section {
+            Section::Header => {
+                let (key, value) = match line.split_once(':') {
+                    Some(pair) => pair,
+                    None => {
+                        return Err(syntax_error(
+                            line_number,
+                            1,
+                            "expected a `Key: value` header line",
+                        ))
+                    }
+                };
+                let padding = value.chars().take_while(|c| c.is_whitespace()).count();
+                let value_column = (key.chars().count() + 1 + padding) as u32 + 1;
+                let value = unescape(value.trim(), line_number, value_column)?;
+
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "game" => match value.to_ascii_lowercase().as_str() {
+                        "crossword" => game = Game::Crossword,
+                        "word search" => game = Game::WordSearch,
+                        _ => {
+                            return Err(syntax_error(
+                                line_number,
+                                value_column,
+                                "expected `Crossword` or `Word Search`",
+                            ))
+                        }
+                    },
+                    "title" | "description" | "author" if value.is_empty() => {
+                        return Err(syntax_error(
+                            line_number,
+                            value_column,
+                            &format!("`{}` can't be empty", key.trim()),
+                        ))
+                    }
+                    "title" => title = Some(value),
+                    "description" => description = Some(value),
+                    "author" => author = Some(value),
+                    "created" => match value.parse::<u32>() {
+                        Ok(timestamp) => creation_date = Some(timestamp),
+                        Err(_) => {
+                            return Err(syntax_error(
+                                line_number,
+                                value_column,
+                                "expected a unix timestamp",
+                            ))
+                        }
+                    },
+                    "diagramless" => match value.to_ascii_lowercase().as_str() {
+                        "yes" => diagramless = true,
+                        "no" => diagramless = false,
+                        _ => {
+                            return Err(syntax_error(
+                                line_number,
+                                value_column,
+                                "expected `yes` or `no`",
+                            ))
+                        }
+                    },
+                    other => {
+                        return Err(syntax_error(
+                            line_number,
+                            1,
+                            &format!("unknown header `{}`", other),
+                        ))
+                    }
+                }
+            }
+            Section::Grid if game == Game::WordSearch => {
+                letters.push(parse_letter_row(line, line_number)?);
+                locations.rows.push((line_number, tokens(line)));
+            }
+            Section::Grid => {
+                grid.push(parse_grid_row(line, line_number)?);
+                locations.rows.push((line_number, tokens(line)));
+            }
+            Section::Across => {
+                let clue = parse_clue(line, line_number)?;
+                locations.across.push((clue.number, line_number));
+                horizontal_clues.push(clue);
+            }
+            Section::Down => {
+                let clue = parse_clue(line, line_number)?;
+                locations.down.push((clue.number, line_number));
+                vertical_clues.push(clue);
+            }
+            Section::Words => {
+                words.push(parse_word(line, line_number)?);
+                locations.words.push(line_number);
+            }
+        }
+    }
+
+    let end_line = last_line + 1;
+    let title = title.ok_or_else(|| syntax_error(end_line, 1, "missing `Title` header"))?;
+    let description =
+        description.ok_or_else(|| syntax_error(end_line, 1, "missing `Description` header"))?;
+    let author = author.ok_or_else(|| syntax_error(end_line, 1, "missing `Author` header"))?;
+
+    let (width, height) = match game {
+        Game::Crossword => (grid.first().map_or(0, |row| row.len()), grid.len()),
+        Game::WordSearch => (letters.first().map_or(0, |row| row.len()), letters.len()),
+    };
+
+    if height == 0 {
+        return Err(syntax_error(end_line, 1, "missing grid"));
+    }
+
+    if width > u8::MAX as usize || height > u8::MAX as usize {
+        return Err(syntax_error(end_line, 1, "grid is larger than 255 boxes"));
+    }
+
+    if diagramless && game == Game::WordSearch {
+        return Err(syntax_error(
+            end_line,
+            1,
+            "only crosswords can be diagramless",
+        ));
+    }
+
+    for (line_number, row) in &locations.rows {
+        if row.len() != width {
+            return Err(syntax_error(
+                *line_number,
+                1,
+                &format!(
+                    "expected {} boxes like the first row, found {}",
+                    width,
+                    row.len()
+                ),
+            ));
+        }
+    }
+
+    let box_numbers: Vec<Vec<u8>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|crossword_box| crossword_box.number)
+                .collect()
+        })
+        .collect();
+
+    let gamedata = match game {
+        Game::Crossword => {
+            let mut crossword = CrosswordData::new(
+                width as u8,
+                height as u8,
+                horizontal_clues,
+                vertical_clues,
+                grid,
+            )
+            .map_err(|err| locations.locate(err, &box_numbers))?;
+            crossword.diagramless = diagramless;
+            GameData::Crossword(crossword)
+        }
+        Game::WordSearch => GameData::WordSearch(
+            WordSearchData::new(width as u8, height as u8, words, letters)
+                .map_err(|err| locations.locate(err, &box_numbers))?,
+        ),
+    };
+
+    Ok(TggFile::assemble(
+        &title,
+        &description,
+        &author,
+        creation_date.unwrap_or_else(current_timestamp),
+        gamedata,
+        Vec::new(),
+    ))
+}
+
+/// Lines and columns of the parsed grid rows, clues and words.
+#[derive(Default)]
+struct Locations<'a> {
+    rows: Vec<(u32, Vec<(u32, &'a str)>)>,
+    across: Vec<(u8, u32)>,
+    down: Vec<(u8, u32)>,
+    words: Vec<u32>,
+}
+
+impl Locations<'_> {
+    /// Turns an error from checking the whole puzzle into a syntax error at the line that
+    /// caused it.
+    fn locate(&self, err: Error, box_numbers: &[Vec<u8>]) -> Error {
+        let clue_line = |clues: &[(u8, u32)], number: u8| {
+            clues
+                .iter()
+                .rev()
+                .find(|(clue_number, _)| *clue_number == number)
+                .map(|(_, line)| *line)
+        };
+
+        let (line, column, reason) = match err {
+            Error::HorizontalClueContainsInvalidNumber { number } => (
+                clue_line(&self.across, number),
+                1,
+                format!("no box is numbered {} for this across clue", number),
+            ),
+            Error::HorizontalClueContainsDuplicate { number } => (
+                clue_line(&self.across, number),
+                1,
+                format!("across clue {} is already listed", number),
+            ),
+            Error::VerticalClueContainsInvalidNumber { number } => (
+                clue_line(&self.down, number),
+                1,
+                format!("no box is numbered {} for this down clue", number),
+            ),
+            Error::VerticalClueContainsDuplicate { number } => (
+                clue_line(&self.down, number),
+                1,
+                format!("down clue {} is already listed", number),
+            ),
+            Error::DuplicateNumber { number } => {
+                let position = box_numbers
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(row, boxes)| {
+                        let column = boxes.iter().rposition(|&other| other == number)?;
+                        Some((row, column))
+                    });
+                match position {
+                    Some((row, column)) => {
+                        let (line, tokens) = &self.rows[row];
+                        (
+                            Some(*line),
+                            tokens[column].0,
+                            format!("box number {} is used more than once", number),
+                        )
+                    }
+                    None => (None, 1, String::new()),
+                }
+            }
+            Error::TooManyWords => (
+                self.words.get(u8::MAX as usize).copied(),
+                1,
+                "word searches can't have more than 255 words".to_string(),
+            ),
+            _ => (None, 1, String::new()),
+        };
+
+        match line {
+            Some(line) => syntax_error(line, column, &reason),
+            None => err,
+        }
+    }
+}
+
+fn parse_letter_row(line: &str, line_number: u32) -> Result<Vec<char>, Error> {
+    let mut row = Vec::new();
+
+    for (column, token) in tokens(line) {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if letter.is_ascii_uppercase() => row.push(letter),
+            _ => {
+                return Err(syntax_error(
+                    line_number,
+                    column,
+                    "expected a single uppercase letter",
+                ))
+            }
+        }
+    }
+
+    Ok(row)
+}
+
+fn parse_word(line: &str, line_number: u32) -> Result<String, Error> {
+    let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+    let word = line.trim();
+
+    if let Some(index) = word.chars().position(|c| !c.is_ascii_uppercase()) {
+        return Err(syntax_error(
+            line_number,
+            (indent + index) as u32 + 1,
+            "words can only contain uppercase letters",
+        ));
+    }
+
+    Ok(word.to_string())
+}
+
+fn parse_grid_row(line: &str, line_number: u32) -> Result<Vec<CrosswordBox>, Error> {
+    let mut row = Vec::new();
+
+    for (column, token) in tokens(line) {
+        let (token, style) = match token.rfind('{') {
+            Some(start) if token.ends_with('}') => {
+                let style_column = column + token[..start].chars().count() as u32;
+                let style = parse_style(
+                    &token[start + 1..token.len() - 1],
+                    line_number,
+                    style_column,
+                )?;
+                (&token[..start], style)
+            }
+            _ => (token, CellStyle::default()),
+        };
+        let digits: String = token.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let rest = &token[digits.len()..];
+        let value_column = column + digits.len() as u32;
+        if rest.is_empty() {
+            return Err(syntax_error(
+                line_number,
+                value_column,
+                "expected `#`, `.`, `~`, a letter or a rebus after the box number",
+            ));
+        }
+
+        let value_length = rest.trim_end_matches(['|', '_']).len().max(1);
+        let bar_marks = &rest[value_length..];
+        let bars = Bars {
+            right: bar_marks.contains('|'),
+            bottom: bar_marks.contains('_'),
+        };
+        let rest = &rest[..value_length];
+
+        let number = if digits.is_empty() {
+            0
+        } else {
+            match digits.parse::<u8>() {
+                Ok(number) if number != 0 => number,
+                _ => {
+                    return Err(syntax_error(
+                        line_number,
+                        column,
+                        "box numbers must be between 1 and 255",
+                    ))
+                }
+            }
+        };
+
+        let mut chars = rest.chars();
+        let value = match (chars.next(), chars.next()) {
+            (Some('#'), None) => CrosswordBoxValue::Solid,
+            (Some('~'), None) => CrosswordBoxValue::Void,
+            (Some('.'), None) => CrosswordBoxValue::Empty,
+            (Some(letter), None) if letter.is_ascii_uppercase() => {
+                CrosswordBoxValue::Letter(letter)
+            }
+            (Some('['), Some(_)) if rest.len() > 2 && rest.ends_with(']') => {
+                let rebus = &rest[1..rest.len() - 1];
+                if validate_rebus(rebus).is_err() {
+                    return Err(syntax_error(
+                        line_number,
+                        value_column,
+                        &format!("invalid rebus `{}`", rebus),
+                    ));
+                }
+                CrosswordBoxValue::Rebus(rebus.to_string())
+            }
+            _ => {
+                return Err(syntax_error(
+                    line_number,
+                    value_column,
+                    &format!("invalid box `{}`", rest),
+                ))
+            }
+        };
+
+        row.push(
+            CrosswordBox::new(number, value)?
+                .with_style(style)
+                .with_bars(bars),
+        );
+    }
+
+    Ok(row)
+}
+
+fn parse_style(styles: &str, line_number: u32, column: u32) -> Result<CellStyle, Error> {
+    let mut style = CellStyle::default();
+
+    for name in styles.split(',') {
+        match name {
+            "circle" => style.circled = true,
+            "bold" => style.bold_border = true,
+            _ => match parse_hex_color(name).filter(|_| name.starts_with('#')) {
+                Some(shade) => style.shade = Some(shade),
+                None => {
+                    return Err(syntax_error(
+                        line_number,
+                        column,
+                        &format!("invalid style `{}`", name),
+                    ))
+                }
+            },
+        }
+    }
+
+    Ok(style)
+}
+
+fn parse_clue(line: &str, line_number: u32) -> Result<CrosswordClue, Error> {
+    let indent = line.len() - line.trim_start().len();
+    let trimmed = line.trim();
+
+    let (number, text) = match trimmed.split_once('.') {
+        Some(pair) => pair,
+        None => {
+            return Err(syntax_error(
+                line_number,
+                indent as u32 + 1,
+                "expected a `<number>. <text>` clue",
+            ))
+        }
+    };
+
+    let number = match number.trim().parse::<u8>() {
+        Ok(number) if number != 0 => number,
+        _ => {
+            return Err(syntax_error(
+                line_number,
+                indent as u32 + 1,
+                "clue numbers must be between 1 and 255",
+            ))
+        }
+    };
+
+    let text_column = (indent + trimmed.len() - text.trim_start().len()) as u32 + 1;
+    let text = unescape(text.trim(), line_number, text_column)?;
+
+    Ok(CrosswordClue::from_full_text(number, &text))
+}
+
+/// Splits a line on whitespace, returning every token with its 1-based column.
+fn tokens(line: &str) -> Vec<(u32, &str)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (index, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(token_start)) => {
+                tokens.push((token_start, &line[token_start..index]));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+
+    if let Some(token_start) = start {
+        tokens.push((token_start, &line[token_start..]));
+    }
+
+    tokens
+        .into_iter()
+        .map(|(start, token)| (line[..start].chars().count() as u32 + 1, token))
+        .collect()
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(value: &str, line: u32, column: u32) -> Result<String, Error> {
+    let mut result = String::new();
+    let mut chars = value.chars().enumerate();
+
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some((_, 'n')) => result.push('\n'),
+            Some((_, '\\')) => result.push('\\'),
+            _ => {
+                return Err(syntax_error(
+                    line,
+                    column + index as u32,
+                    "invalid escape sequence",
+                ))
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn syntax_error(line: u32, column: u32, reason: &str) -> Error {
+    Error::TextSyntax {
+        line,
+        column,
+        reason: reason.to_string(),
+    }
+}
@@ -6,15 +6,7 @@ pub fn calculate_checksum(bytes: Vec<u8>) -> [u8; 2] {
         .to_le_bytes()[0..2]
         .to_vec();
 
-    return [checksum[0], checksum[1]];
-}
-
-pub fn extract_cstring(bytes: &[u8]) -> String {
-    bytes
-        .iter()
-        .take_while(|&&byte| byte != 0x00)
-        .map(|&byte| byte as char)
-        .collect()
+    [checksum[0], checksum[1]]
 }
 
 pub fn extract_cstring_with_offset(bytes: &[u8], start: usize) -> (String, usize) {
@@ -28,3 +20,59 @@ pub fn extract_cstring_with_offset(bytes: &[u8], start: usize) -> (String, usize
         .collect::<String>();
     (result, end + 1) // +1 to skip the null terminator
 }
+
+/// Small deterministic random number generator (SplitMix64), so generated puzzles can be
+/// reproduced from their seed.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, `bound` must not be 0.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+/// Formats a coordinate without trailing zeros.
+pub(crate) fn number(value: f32) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Formats a color as `#rrggbb`.
+pub fn hex_color(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Parses `rrggbb` or `#rrggbb`.
+pub fn parse_hex_color(text: &str) -> Option<[u8; 3]> {
+    let text = text.strip_prefix('#').unwrap_or(text);
+    if text.len() != 6 || !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&text[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
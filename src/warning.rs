@@ -0,0 +1,37 @@
+/// Something that could not be carried over when converting to or from another format.
+/// The conversion still succeeds, but the result is missing the reported feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    // -- Puz
+    PuzRebusIgnored { row: u8, column: u8 },
+    PuzStyleIgnored { row: u8, column: u8 },
+    PuzVoidCellIgnored { row: u8, column: u8 },
+    PuzExtensionIgnored { name: String },
+    PuzChecksumMismatch { section: String },
+    PuzCopyrightIgnored,
+    PuzClueIgnored { number: u8 },
+    // -- Ipuz
+    IpuzCellIgnored { row: u8, column: u8 },
+    IpuzRebusIgnored { row: u8, column: u8 },
+    IpuzFieldIgnored { field: String },
+    // -- Jpz
+    JpzRebusIgnored { row: u8, column: u8 },
+    JpzStyleIgnored { row: u8, column: u8 },
+    JpzWordIgnored { id: String },
+    JpzClueIgnored { word: String },
+    JpzDiagramlessIgnored,
+    // -- Word Search Generator
+    WordNotPlaced { word: String },
+    WordInsideAnother { word: String, other: String },
+    WordFoundTwice { word: String },
+    // -- Shared
+    MissingMetadata { field: String },
+    UnsupportedCharacter { row: u8, column: u8, found: char },
+    NonLatin1Text,
+}
+
+impl core::fmt::Display for Warning {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
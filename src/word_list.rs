@@ -0,0 +1,232 @@
+//! Scored word lists used to fill, generate and solve puzzles.
+//!
+//! The text format is one word per line, optionally followed by `;` and a score, like
+//! `CROSSWORD;60`. Spaces and punctuation are dropped from words, so `ICE CREAM` becomes
+//! `ICECREAM`, and words with letters outside of A to Z are skipped.
+
+use crate::Error;
+use std::collections::HashMap;
+
+/// Score of words listed without one.
+pub const DEFAULT_SCORE: u32 = 50;
+
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    words: Vec<String>,
+    scores: Vec<u32>,
+    /// Position of every word in the index for its length.
+    positions: Vec<usize>,
+    lookup: HashMap<String, usize>,
+    by_length: Vec<LengthIndex>,
+}
+
+/// The words of one length, with a bitset of the words having each letter at each
+/// position so patterns can be matched without looking at every word.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LengthIndex {
+    pub words: Vec<usize>,
+    letters: Vec<[Vec<u64>; 26]>,
+}
+
+impl WordList {
+    pub fn new() -> WordList {
+        WordList::default()
+    }
+
+    pub fn parse(text: &str) -> Result<WordList, Error> {
+        let mut list = WordList::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, score) = match line.rsplit_once(';') {
+                Some((word, score)) => match score.trim().parse::<u32>() {
+                    Ok(score) => (word, score),
+                    Err(_) => {
+                        return Err(Error::InvalidWordListScore {
+                            line: index as u32 + 1,
+                        })
+                    }
+                },
+                None => (line, DEFAULT_SCORE),
+            };
+
+            list.insert(word, score);
+        }
+
+        Ok(list)
+    }
+
+    /// Adds a word, keeping the higher score if it's already listed. Returns false if
+    /// the word has no letters or letters outside of A to Z.
+    pub fn insert(&mut self, word: &str, score: u32) -> bool {
+        let word = match normalize(word) {
+            Some(word) => word,
+            None => return false,
+        };
+
+        if let Some(&id) = self.lookup.get(&word) {
+            self.scores[id] = self.scores[id].max(score);
+            return true;
+        }
+
+        let id = self.words.len();
+        let length = word.len();
+
+        if self.by_length.len() <= length {
+            self.by_length.resize_with(length + 1, LengthIndex::default);
+        }
+        let index = &mut self.by_length[length];
+        let position = index.words.len();
+        index.words.push(id);
+        if index.letters.is_empty() {
+            index.letters = vec![Default::default(); length];
+        }
+        for (letters, byte) in index.letters.iter_mut().zip(word.bytes()) {
+            let bits = &mut letters[(byte - b'A') as usize];
+            bits.resize(position / 64 + 1, 0);
+            bits[position / 64] |= 1 << (position % 64);
+        }
+
+        self.lookup.insert(word.clone(), id);
+        self.words.push(word);
+        self.scores.push(score);
+        self.positions.push(position);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.score(word).is_some()
+    }
+
+    pub fn score(&self, word: &str) -> Option<u32> {
+        let word = normalize(word)?;
+        self.lookup.get(&word).map(|&id| self.scores[id])
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .zip(&self.scores)
+            .map(|(word, score)| (word.as_str(), *score))
+    }
+
+    /// Words matching a pattern where `?` stands for any letter, like `C?T`.
+    pub fn matching(&self, pattern: &str) -> Vec<&str> {
+        let pattern: Vec<Option<u8>> = pattern
+            .bytes()
+            .map(|byte| match byte {
+                b'?' => None,
+                byte => Some(byte.to_ascii_uppercase()),
+            })
+            .collect();
+
+        let index = match self.index(pattern.len()) {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+
+        let mut matches = index.all();
+        if !index.restrict(&mut matches, &pattern) {
+            return Vec::new();
+        }
+
+        bits(&matches)
+            .map(|position| self.words[index.words[position]].as_str())
+            .collect()
+    }
+
+    pub(crate) fn word(&self, id: usize) -> &str {
+        &self.words[id]
+    }
+
+    pub(crate) fn id(&self, word: &str) -> Option<usize> {
+        self.lookup.get(word).copied()
+    }
+
+    pub(crate) fn position(&self, id: usize) -> usize {
+        self.positions[id]
+    }
+
+    pub(crate) fn word_score(&self, id: usize) -> u32 {
+        self.scores[id]
+    }
+
+    pub(crate) fn index(&self, length: usize) -> Option<&LengthIndex> {
+        self.by_length
+            .get(length)
+            .filter(|index| !index.words.is_empty())
+    }
+}
+
+impl LengthIndex {
+    /// A bitset with every word of this length.
+    pub fn all(&self) -> Vec<u64> {
+        let mut all = vec![u64::MAX; (self.words.len() + 63) / 64];
+        if self.words.len() % 64 != 0 {
+            if let Some(last) = all.last_mut() {
+                *last = (1 << (self.words.len() % 64)) - 1;
+            }
+        }
+        all
+    }
+
+    /// Removes the words that don't fit the known letters. Returns false when no word is
+    /// left, or when a pattern letter isn't A to Z.
+    pub fn restrict(&self, candidates: &mut [u64], pattern: &[Option<u8>]) -> bool {
+        for (position, letter) in pattern.iter().enumerate() {
+            let letter = match letter {
+                Some(letter) if letter.is_ascii_uppercase() => letter,
+                Some(_) => return false,
+                None => continue,
+            };
+
+            let letters = &self.letters[position][(letter - b'A') as usize];
+            for (index, word) in candidates.iter_mut().enumerate() {
+                *word &= letters.get(index).copied().unwrap_or(0);
+            }
+        }
+
+        candidates.iter().any(|word| *word != 0)
+    }
+}
+
+/// Positions of the set bits.
+pub(crate) fn bits(set: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    set.iter().enumerate().flat_map(|(index, word)| {
+        (0..64)
+            .filter(move |bit| word & (1 << bit) != 0)
+            .map(move |bit| index * 64 + bit)
+    })
+}
+
+/// The letters of a word in uppercase, with spaces and punctuation dropped. `None` if
+/// the word has no letters or letters outside of A to Z.
+pub(crate) fn normalize(word: &str) -> Option<String> {
+    let mut normalized = String::new();
+
+    for c in word.chars() {
+        if c.is_ascii_alphabetic() {
+            normalized.push(c.to_ascii_uppercase());
+        } else if c.is_alphanumeric() {
+            return None;
+        }
+    }
+
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
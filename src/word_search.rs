@@ -0,0 +1,185 @@
+use crate::utils::extract_cstring_with_offset;
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordSearchData {
+    pub width: u8,
+    pub height: u8,
+    pub words: Vec<String>,
+    pub grid: Vec<Vec<char>>,
+}
+
+impl WordSearchData {
+    pub fn load(bytes: &[u8]) -> Result<WordSearchData, Error> {
+        let mut offset = 0;
+        if bytes.len() < 3 {
+            return Err(Error::UnexpectedEndOfFile);
+        }
+        let width = bytes[offset];
+        offset += 1;
+        let height = bytes[offset];
+        offset += 1;
+        let total_words = bytes[offset];
+        offset += 1;
+
+        if width == 0 || height == 0 {
+            return Err(Error::WidthOrHeightIsZero);
+        }
+
+        let mut words = Vec::new();
+        for _ in 0..total_words {
+            if offset >= bytes.len() {
+                return Err(Error::UnexpectedEndOfFile);
+            }
+            let (word, new_offset) = extract_cstring_with_offset(bytes, offset);
+            words.push(word);
+            offset = new_offset;
+        }
+
+        let expected_bytes = offset + width as usize * height as usize;
+        if expected_bytes != bytes.len() {
+            return Err(Error::NotEnoughWordSearchBytes {
+                expected: expected_bytes as u32,
+                found: bytes.len() as u32,
+            });
+        }
+
+        let grid = bytes[offset..]
+            .chunks(width as usize)
+            .map(|row| row.iter().map(|&byte| byte as char).collect())
+            .collect();
+
+        WordSearchData::new(width, height, words, grid)
+    }
+
+    pub fn new(
+        width: u8,
+        height: u8,
+        words: Vec<String>,
+        grid: Vec<Vec<char>>,
+    ) -> Result<WordSearchData, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::WidthOrHeightIsZero);
+        }
+
+        if grid.len() != height as usize {
+            return Err(Error::HeightWordSearchDataMismatch {
+                height,
+                grid_height: grid.len() as u32,
+            });
+        }
+
+        for row in &grid {
+            if row.len() != width as usize {
+                return Err(Error::WidthWordSearchDataMismatch {
+                    width,
+                    grid_width: row.len() as u32,
+                });
+            }
+
+            for letter in row {
+                validate_letter(*letter)?;
+            }
+        }
+
+        if words.len() > u8::MAX as usize {
+            return Err(Error::TooManyWords);
+        }
+
+        for word in &words {
+            if word.is_empty() {
+                return Err(Error::WordIsEmpty);
+            }
+
+            for letter in word.chars() {
+                validate_letter(letter)?;
+            }
+        }
+
+        Ok(WordSearchData {
+            width,
+            height,
+            words,
+            grid,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.width, self.height, self.words.len() as u8];
+
+        for word in &self.words {
+            bytes.extend(word.as_bytes());
+            bytes.push(0);
+        }
+
+        for row in &self.grid {
+            for letter in row {
+                bytes.push(*letter as u8);
+            }
+        }
+
+        bytes
+    }
+}
+
+/// The eight directions a word can run in, reading from its first letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WordDirection {
+    Right,
+    Left,
+    Down,
+    Up,
+    DownRight,
+    DownLeft,
+    UpRight,
+    UpLeft,
+}
+
+impl WordDirection {
+    pub const ALL: [WordDirection; 8] = [
+        WordDirection::Right,
+        WordDirection::Left,
+        WordDirection::Down,
+        WordDirection::Up,
+        WordDirection::DownRight,
+        WordDirection::DownLeft,
+        WordDirection::UpRight,
+        WordDirection::UpLeft,
+    ];
+
+    /// Row and column change from one letter to the next.
+    pub fn steps(&self) -> (i32, i32) {
+        match self {
+            WordDirection::Right => (0, 1),
+            WordDirection::Left => (0, -1),
+            WordDirection::Down => (1, 0),
+            WordDirection::Up => (-1, 0),
+            WordDirection::DownRight => (1, 1),
+            WordDirection::DownLeft => (1, -1),
+            WordDirection::UpRight => (-1, 1),
+            WordDirection::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Whether the word reads right to left or bottom to top.
+    pub fn is_reversed(&self) -> bool {
+        let (row_step, column_step) = self.steps();
+        column_step < 0 || (column_step == 0 && row_step < 0)
+    }
+}
+
+fn validate_letter(letter: char) -> Result<(), Error> {
+    if !letter.is_ascii() {
+        return Err(Error::NonAsciiCharacter);
+    }
+
+    if !letter.is_alphabetic() {
+        return Err(Error::NonAlphabeticCharacter);
+    }
+
+    if letter.is_lowercase() {
+        return Err(Error::NonUppercaseCharacter);
+    }
+
+    Ok(())
+}
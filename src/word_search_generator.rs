@@ -0,0 +1,319 @@
+//! Generates word search puzzles from a word list.
+//!
+//! Words are placed longest first, preferring spots where they share letters with words
+//! already in the grid. The remaining boxes get random letters, which are redrawn
+//! wherever they spell one of the words a second time. Words inside a longer word,
+//! forwards or backwards, are left out since they would always be found twice.
+
+use crate::utils::Rng;
+use crate::word_list::normalize;
+use crate::word_search::{WordDirection, WordSearchData};
+use crate::word_search_solver::{find_words, WordLocation};
+use crate::{current_timestamp, Error, GameData, TggFile, Warning};
+use std::cmp::Reverse;
+
+/// How often filler letters are redrawn before accidental words are left in place with
+/// a warning.
+const MAX_FILL_ROUNDS: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct WordSearchOptions {
+    pub width: u8,
+    pub height: u8,
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub diagonal: bool,
+    /// Also allows the enabled directions backwards.
+    pub reversed: bool,
+    /// The same seed always gives the same puzzle.
+    pub seed: u64,
+}
+
+impl Default for WordSearchOptions {
+    fn default() -> WordSearchOptions {
+        WordSearchOptions {
+            width: 12,
+            height: 12,
+            horizontal: true,
+            vertical: true,
+            diagonal: true,
+            reversed: false,
+            seed: 0,
+        }
+    }
+}
+
+impl WordSearchOptions {
+    fn directions(&self) -> Vec<WordDirection> {
+        WordDirection::ALL
+            .into_iter()
+            .filter(|direction| {
+                let (row_step, column_step) = direction.steps();
+                let allowed = match (row_step, column_step) {
+                    (0, _) => self.horizontal,
+                    (_, 0) => self.vertical,
+                    _ => self.diagonal,
+                };
+                allowed && (self.reversed || !direction.is_reversed())
+            })
+            .collect()
+    }
+}
+
+impl TggFile {
+    /// Generates a word search. Spaces and punctuation are dropped from words, words
+    /// that can't be placed are left out with a warning, as are words inside another
+    /// word. Words that are still spelled more than once in the finished grid are
+    /// reported too.
+    pub fn generate_word_search(
+        title: &str,
+        description: &str,
+        author: &str,
+        words: &[&str],
+        options: &WordSearchOptions,
+    ) -> Result<(TggFile, Vec<Warning>), Error> {
+        if options.width == 0 || options.height == 0 {
+            return Err(Error::WidthOrHeightIsZero);
+        }
+
+        let mut warnings = Vec::new();
+        let mut rng = Rng::new(options.seed);
+        let directions = options.directions();
+        let (width, height) = (options.width as usize, options.height as usize);
+        let mut grid: Vec<Vec<Option<u8>>> = vec![vec![None; width]; height];
+
+        let mut order: Vec<(&str, Option<Vec<u8>>)> = words
+            .iter()
+            .map(|word| (*word, normalize(word).map(String::into_bytes)))
+            .collect();
+        order.sort_by_key(|(_, letters)| Reverse(letters.as_ref().map_or(0, Vec::len)));
+
+        let mut placed: Vec<Placed> = Vec::new();
+        for (word, letters) in order {
+            let letters = match letters {
+                Some(letters) if !placed.iter().any(|other| other.letters == letters) => letters,
+                _ => {
+                    warnings.push(not_placed(word));
+                    continue;
+                }
+            };
+
+            let reversed: Vec<u8> = letters.iter().rev().copied().collect();
+            if let Some(other) = placed.iter().find(|other| {
+                contains(&other.letters, &letters) || contains(&other.letters, &reversed)
+            }) {
+                warnings.push(Warning::WordInsideAnother {
+                    word: word.to_string(),
+                    other: String::from_utf8_lossy(&other.letters).to_string(),
+                });
+                continue;
+            }
+
+            match place(&mut grid, &letters, &directions, &mut rng) {
+                Some(cells) => placed.push(Placed { letters, cells }),
+                None => warnings.push(not_placed(word)),
+            }
+        }
+
+        if placed.len() > u8::MAX as usize {
+            return Err(Error::TooManyWords);
+        }
+
+        let mut fixed = vec![vec![false; width]; height];
+        for word in &placed {
+            for &(row, column) in &word.cells {
+                fixed[row][column] = true;
+            }
+        }
+
+        let mut letters: Vec<Vec<u8>> = grid
+            .iter()
+            .map(|row| row.iter().map(|letter| letter.unwrap_or(b'A')).collect())
+            .collect();
+        for (row, cells) in letters.iter_mut().enumerate() {
+            for (column, letter) in cells.iter_mut().enumerate() {
+                if !fixed[row][column] {
+                    *letter = random_letter(&mut rng);
+                }
+            }
+        }
+
+        let mut duplicates = find_duplicates(&letters, &placed);
+        for _ in 0..MAX_FILL_ROUNDS {
+            let filler: Vec<(usize, usize)> = duplicates
+                .iter()
+                .flat_map(|(_, location)| location.cells())
+                .map(|(row, column)| (row as usize, column as usize))
+                .filter(|&(row, column)| !fixed[row][column])
+                .collect();
+            // Words spelled by placed letters alone can't be redrawn
+            if filler.is_empty() {
+                break;
+            }
+            for (row, column) in filler {
+                letters[row][column] = random_letter(&mut rng);
+            }
+            duplicates = find_duplicates(&letters, &placed);
+        }
+
+        let mut repeated: Vec<usize> = duplicates.into_iter().map(|(id, _)| id).collect();
+        repeated.sort();
+        repeated.dedup();
+        for id in repeated {
+            warnings.push(Warning::WordFoundTwice {
+                word: String::from_utf8_lossy(&placed[id].letters).to_string(),
+            });
+        }
+
+        let mut words: Vec<String> = placed
+            .into_iter()
+            .map(|word| String::from_utf8_lossy(&word.letters).to_string())
+            .collect();
+        words.sort();
+
+        let grid = letters
+            .into_iter()
+            .map(|row| row.into_iter().map(char::from).collect())
+            .collect();
+        let word_search = WordSearchData::new(options.width, options.height, words, grid)?;
+
+        let tgg_file = TggFile::assemble(
+            title,
+            description,
+            author,
+            current_timestamp(),
+            GameData::WordSearch(word_search),
+            Vec::new(),
+        );
+
+        Ok((tgg_file, warnings))
+    }
+}
+
+struct Placed {
+    letters: Vec<u8>,
+    cells: Vec<(usize, usize)>,
+}
+
+/// Places a word at a random spot among the ones sharing the most letters with the
+/// grid, returning its boxes.
+fn place(
+    grid: &mut [Vec<Option<u8>>],
+    letters: &[u8],
+    directions: &[WordDirection],
+    rng: &mut Rng,
+) -> Option<Vec<(usize, usize)>> {
+    let mut best_overlap = 0;
+    let mut candidates: Vec<Vec<(usize, usize)>> = Vec::new();
+
+    for row in 0..grid.len() {
+        for column in 0..grid[row].len() {
+            for direction in directions {
+                let cells = match cells(grid, letters.len(), row, column, *direction) {
+                    Some(cells) => cells,
+                    None => continue,
+                };
+
+                let mut overlap = 0;
+                let fits = cells.iter().zip(letters).all(|(&(row, column), letter)| {
+                    match grid[row][column] {
+                        Some(existing) if existing == *letter => {
+                            overlap += 1;
+                            true
+                        }
+                        Some(_) => false,
+                        None => true,
+                    }
+                });
+
+                // A word entirely inside another one would be found twice
+                if !fits || overlap == letters.len() {
+                    continue;
+                }
+
+                if overlap > best_overlap {
+                    best_overlap = overlap;
+                    candidates.clear();
+                }
+                if overlap == best_overlap {
+                    candidates.push(cells);
+                }
+            }
+        }
+    }
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let cells = candidates.swap_remove(rng.below(candidates.len()));
+    for (&(row, column), letter) in cells.iter().zip(letters) {
+        grid[row][column] = Some(*letter);
+    }
+
+    Some(cells)
+}
+
+/// The boxes of a word starting at the given box, if it stays inside the grid.
+fn cells<T>(
+    grid: &[Vec<T>],
+    length: usize,
+    row: usize,
+    column: usize,
+    direction: WordDirection,
+) -> Option<Vec<(usize, usize)>> {
+    let (row_step, column_step) = direction.steps();
+
+    (0..length as i32)
+        .map(|offset| {
+            let row = row as i32 + row_step * offset;
+            let column = column as i32 + column_step * offset;
+            if row < 0 || column < 0 {
+                return None;
+            }
+
+            let (row, column) = (row as usize, column as usize);
+            grid.get(row)
+                .and_then(|cells| cells.get(column))
+                .map(|_| (row, column))
+        })
+        .collect()
+}
+
+/// Every place a word is spelled other than where it was placed, in any of the eight
+/// directions.
+fn find_duplicates(letters: &[Vec<u8>], placed: &[Placed]) -> Vec<(usize, WordLocation)> {
+    let words: Vec<&str> = placed
+        .iter()
+        .map(|word| std::str::from_utf8(&word.letters).unwrap_or_default())
+        .collect();
+
+    find_words(letters, &words)
+        .into_iter()
+        .filter(|(id, location)| {
+            let mut cells: Vec<(usize, usize)> = location
+                .cells()
+                .into_iter()
+                .map(|(row, column)| (row as usize, column as usize))
+                .collect();
+            let mut placed_cells = placed[*id].cells.clone();
+            cells.sort();
+            placed_cells.sort();
+            cells != placed_cells
+        })
+        .collect()
+}
+
+fn contains(letters: &[u8], part: &[u8]) -> bool {
+    letters.windows(part.len()).any(|window| window == part)
+}
+
+fn random_letter(rng: &mut Rng) -> u8 {
+    b'A' + rng.below(26) as u8
+}
+
+fn not_placed(word: &str) -> Warning {
+    Warning::WordNotPlaced {
+        word: word.to_string(),
+    }
+}
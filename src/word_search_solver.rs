@@ -0,0 +1,226 @@
+//! Finds words in word search grids.
+//!
+//! All words go into one trie, then every box and direction is walked once, following
+//! the trie for as long as the letters match. A grid is searched in a single pass no
+//! matter how many words are looked for.
+
+use crate::word_list::WordList;
+use crate::word_search::{WordDirection, WordSearchData};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordLocation {
+    pub word: String,
+    /// Row and column of the first letter.
+    pub row: u8,
+    pub column: u8,
+    pub direction: WordDirection,
+    pub length: u8,
+}
+
+impl WordLocation {
+    /// Row and column of every letter, from the first one.
+    pub fn cells(&self) -> Vec<(u8, u8)> {
+        let (row_step, column_step) = self.direction.steps();
+
+        (0..self.length as i32)
+            .map(|offset| {
+                (
+                    (self.row as i32 + row_step * offset) as u8,
+                    (self.column as i32 + column_step * offset) as u8,
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WordSearchSolution {
+    /// Every place a listed word is spelled, in grid order.
+    pub found: Vec<WordLocation>,
+    /// Listed words that aren't in the grid.
+    pub missing: Vec<String>,
+    /// Listed words that are in the grid more than once.
+    pub ambiguous: Vec<String>,
+    /// Dictionary words in the grid that aren't listed.
+    pub unlisted: Vec<WordLocation>,
+}
+
+impl WordSearchData {
+    pub fn solve(&self) -> WordSearchSolution {
+        self.solve_with_dictionary(&WordList::new(), 0)
+    }
+
+    /// Also reports dictionary words of at least `min_length` letters that are in the grid
+    /// but not listed.
+    pub fn solve_with_dictionary(
+        &self,
+        dictionary: &WordList,
+        min_length: usize,
+    ) -> WordSearchSolution {
+        let mut words: Vec<&str> = self.words.iter().map(String::as_str).collect();
+        let listed = words.len();
+        let listed_words: HashSet<&str> = words.iter().copied().collect();
+        words.extend(
+            dictionary
+                .words()
+                .map(|(word, _)| word)
+                .filter(|word| word.len() >= min_length && !listed_words.contains(word)),
+        );
+
+        let grid: Vec<Vec<u8>> = self
+            .grid
+            .iter()
+            .map(|row| row.iter().map(|letter| *letter as u8).collect())
+            .collect();
+
+        let mut solution = WordSearchSolution::default();
+        for (id, location) in find_words(&grid, &words) {
+            if id < listed {
+                solution.found.push(location);
+            } else {
+                solution.unlisted.push(location);
+            }
+        }
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for found in &solution.found {
+            *counts.entry(found.word.as_str()).or_default() += 1;
+        }
+        for word in &self.words {
+            match counts.get(word.as_str()).copied().unwrap_or_default() {
+                0 => solution.missing.push(word.clone()),
+                1 => {}
+                _ => solution.ambiguous.push(word.clone()),
+            }
+        }
+
+        solution
+    }
+
+    /// Where a listed word is, if it's in the grid exactly once.
+    pub fn locate(&self, word: &str) -> Option<WordLocation> {
+        let solution = self.solve();
+        let mut locations = solution
+            .found
+            .into_iter()
+            .filter(|found| found.word == word);
+
+        match (locations.next(), locations.next()) {
+            (Some(location), None) => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Whether any listed word is missing from the grid or in it more than once.
+    pub fn is_ambiguous(&self) -> bool {
+        let solution = self.solve();
+        !solution.missing.is_empty() || !solution.ambiguous.is_empty()
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    /// Index of the word ending here.
+    word: Option<usize>,
+}
+
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    fn new(words: &[&str]) -> Trie {
+        let mut trie = Trie {
+            nodes: vec![Node::default()],
+        };
+
+        for (id, word) in words.iter().enumerate() {
+            let mut node = 0;
+            for letter in word.bytes() {
+                node = match trie.child(node, letter) {
+                    Some(child) => child,
+                    None => {
+                        trie.nodes.push(Node::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.push((letter, child));
+                        child
+                    }
+                };
+            }
+            // Repeated words keep their first index
+            trie.nodes[node].word.get_or_insert(id);
+        }
+
+        trie
+    }
+
+    fn child(&self, node: usize, letter: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(child_letter, _)| *child_letter == letter)
+            .map(|(_, child)| *child)
+    }
+}
+
+/// Every place one of the words is spelled, with the index of the word. A word read over
+/// the same boxes in both directions, like a palindrome, is only reported once.
+pub(crate) fn find_words(grid: &[Vec<u8>], words: &[&str]) -> Vec<(usize, WordLocation)> {
+    let trie = Trie::new(words);
+    let mut found: Vec<(usize, WordLocation)> = Vec::new();
+    // Word, first and last box of every hit. Walks from different boxes or in different
+    // directions only cover the same boxes when one is the other one backwards.
+    let mut seen = HashSet::new();
+
+    for row in 0..grid.len() {
+        for column in 0..grid[row].len() {
+            for direction in WordDirection::ALL {
+                let (row_step, column_step) = direction.steps();
+                let (mut current_row, mut current_column) = (row as i32, column as i32);
+                let mut node = 0;
+                let mut length = 0;
+
+                while let Some(&letter) = cell(grid, current_row, current_column) {
+                    node = match trie.child(node, letter) {
+                        Some(child) => child,
+                        None => break,
+                    };
+                    length += 1;
+
+                    if let Some(id) = trie.nodes[node].word {
+                        let start = (row as i32, column as i32);
+                        let end = (current_row, current_column);
+                        if !seen.contains(&(id, end, start)) {
+                            seen.insert((id, start, end));
+                            found.push((
+                                id,
+                                WordLocation {
+                                    word: words[id].to_string(),
+                                    row: row as u8,
+                                    column: column as u8,
+                                    direction,
+                                    length,
+                                },
+                            ));
+                        }
+                    }
+
+                    current_row += row_step;
+                    current_column += column_step;
+                }
+            }
+        }
+    }
+
+    found
+}
+
+fn cell(grid: &[Vec<u8>], row: i32, column: i32) -> Option<&u8> {
+    if row < 0 || column < 0 {
+        return None;
+    }
+    grid.get(row as usize)
+        .and_then(|cells| cells.get(column as usize))
+}
@@ -0,0 +1,391 @@
+//! Minimal XML reader and writer, just enough for the JPZ format.
+//!
+//! Namespaces are ignored, elements are matched by their local name.
+
+use crate::Error;
+
+/// Elements nested deeper than this are rejected instead of overflowing the stack.
+/// JPZ files never need more than a few levels.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Element {
+        Element {
+            name: name.to_string(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Element {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_child(mut self, child: Element) -> Element {
+        self.children.push(Node::Element(child));
+        self
+    }
+
+    pub fn with_text(mut self, text: &str) -> Element {
+        self.children.push(Node::Text(text.to_string()));
+        self
+    }
+
+    pub fn parse(text: &str) -> Result<Element, Error> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            offset: 0,
+            depth: 0,
+        };
+
+        parser.skip_misc()?;
+        let root = parser.parse_element()?;
+        parser.skip_misc()?;
+
+        if parser.offset != parser.chars.len() {
+            return Err(parser.error("unexpected content after the root element"));
+        }
+
+        Ok(root)
+    }
+
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| local_name(attribute) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements()
+            .filter(move |element| element.local_name() == name)
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.local_name() == name)
+    }
+
+    /// Finds the first element with the given name, searching depth first.
+    pub fn find(&self, name: &str) -> Option<&Element> {
+        if self.local_name() == name {
+            return Some(self);
+        }
+
+        self.elements().find_map(|element| element.find(name))
+    }
+
+    /// The text content of the element and all of its descendants.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+
+        for child in &self.children {
+            match child {
+                Node::Text(value) => text.push_str(value),
+                Node::Element(element) => text.push_str(&element.text()),
+            }
+        }
+
+        text
+    }
+
+    pub fn to_document(&self) -> String {
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.write(&mut output, 0);
+        output
+    }
+
+    fn write(&self, output: &mut String, indent: usize) {
+        push_indent(output, indent);
+        output.push('<');
+        output.push_str(&self.name);
+        for (name, value) in &self.attributes {
+            output.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+        }
+
+        if self.children.is_empty() {
+            output.push_str("/>\n");
+            return;
+        }
+
+        // Elements with text are written on one line so no whitespace is added to the text
+        if self
+            .children
+            .iter()
+            .any(|child| matches!(child, Node::Text(_)))
+        {
+            output.push('>');
+            for child in &self.children {
+                match child {
+                    Node::Text(text) => output.push_str(&escape(text)),
+                    Node::Element(element) => {
+                        let mut inline = String::new();
+                        element.write(&mut inline, 0);
+                        output.push_str(inline.trim_end());
+                    }
+                }
+            }
+            output.push_str(&format!("</{}>\n", self.name));
+            return;
+        }
+
+        output.push_str(">\n");
+        for element in self.elements() {
+            element.write(output, indent + 1);
+        }
+        push_indent(output, indent);
+        output.push_str(&format!("</{}>\n", self.name));
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    match name.split_once(':') {
+        Some((_, local)) => local,
+        None => name,
+    }
+}
+
+fn push_indent(output: &mut String, indent: usize) {
+    for _ in 0..indent {
+        output.push_str("  ");
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct Parser {
+    chars: Vec<char>,
+    offset: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn error(&self, reason: &str) -> Error {
+        Error::XmlSyntax {
+            offset: self.offset as u32,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.offset).copied()
+    }
+
+    fn starts_with(&self, pattern: &str) -> bool {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(index, c)| self.chars.get(self.offset + index) == Some(&c))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<(), Error> {
+        while !self.starts_with(end) {
+            if self.peek().is_none() {
+                return Err(self.error(&format!("expected `{}`", end)));
+            }
+            self.offset += 1;
+        }
+        self.offset += end.chars().count();
+        Ok(())
+    }
+
+    /// Skips whitespace, comments, processing instructions and doctype declarations.
+    fn skip_misc(&mut self) -> Result<(), Error> {
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.starts_with("<!DOCTYPE") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, Error> {
+        let start = self.offset;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+        {
+            self.offset += 1;
+        }
+
+        if start == self.offset {
+            return Err(self.error("expected a name"));
+        }
+
+        Ok(self.chars[start..self.offset].iter().collect())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected)));
+        }
+        self.offset += 1;
+        Ok(())
+    }
+
+    fn parse_element(&mut self) -> Result<Element, Error> {
+        self.expect('<')?;
+        let name = self.parse_name()?;
+        let mut element = Element::new(&name);
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('/') => {
+                    self.offset += 1;
+                    self.expect('>')?;
+                    return Ok(element);
+                }
+                Some('>') => {
+                    self.offset += 1;
+                    break;
+                }
+                Some(_) => {
+                    let attribute = self.parse_name()?;
+                    self.skip_whitespace();
+                    self.expect('=')?;
+                    self.skip_whitespace();
+                    let quote = match self.peek() {
+                        Some(quote) if quote == '"' || quote == '\'' => quote,
+                        _ => return Err(self.error("expected a quoted attribute value")),
+                    };
+                    self.offset += 1;
+                    let start = self.offset;
+                    while self.peek().is_some_and(|c| c != quote) {
+                        self.offset += 1;
+                    }
+                    let raw: String = self.chars[start..self.offset].iter().collect();
+                    self.expect(quote)?;
+                    element.attributes.push((attribute, self.unescape(&raw)?));
+                }
+                None => return Err(self.error("unexpected end of input")),
+            }
+        }
+
+        loop {
+            if self.starts_with("</") {
+                self.offset += 2;
+                let closing = self.parse_name()?;
+                if closing != name {
+                    return Err(self.error(&format!("expected `</{}>`", name)));
+                }
+                self.skip_whitespace();
+                self.expect('>')?;
+                return Ok(element);
+            } else if self.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.starts_with("<![CDATA[") {
+                self.offset += 9;
+                let start = self.offset;
+                self.skip_until("]]>")?;
+                let text: String = self.chars[start..self.offset - 3].iter().collect();
+                element.children.push(Node::Text(text));
+            } else if self.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.starts_with("<") {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error("too deeply nested"));
+                }
+                self.depth += 1;
+                let child = self.parse_element();
+                self.depth -= 1;
+                element.children.push(Node::Element(child?));
+            } else if self.peek().is_none() {
+                return Err(self.error(&format!("expected `</{}>`", name)));
+            } else {
+                let start = self.offset;
+                while self.peek().is_some_and(|c| c != '<') {
+                    self.offset += 1;
+                }
+                let raw: String = self.chars[start..self.offset].iter().collect();
+                // Whitespace between elements is only formatting
+                if !raw.trim().is_empty() {
+                    element.children.push(Node::Text(self.unescape(&raw)?));
+                }
+            }
+        }
+    }
+
+    fn unescape(&self, raw: &str) -> Result<String, Error> {
+        let mut result = String::new();
+        let mut rest = raw;
+
+        while let Some(start) = rest.find('&') {
+            result.push_str(&rest[..start]);
+            let end = match rest[start..].find(';') {
+                Some(end) => start + end,
+                None => return Err(self.error("unterminated entity")),
+            };
+
+            let entity = &rest[start + 1..end];
+            let value = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => {
+                    let code = if let Some(hex) = entity.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(decimal) = entity.strip_prefix('#') {
+                        decimal.parse::<u32>().ok()
+                    } else {
+                        None
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+
+            match value {
+                Some(c) => result.push(c),
+                None => return Err(self.error(&format!("unknown entity `&{};`", entity))),
+            }
+            rest = &rest[end + 1..];
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+}
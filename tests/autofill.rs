@@ -0,0 +1,150 @@
+mod common;
+
+use common::fixture_crossword;
+use tgg::autofill::AutofillOptions;
+use tgg::crossword::{CrosswordBoxValue, CrosswordData};
+use tgg::entry::Direction;
+use tgg::word_list::WordList;
+
+const WORDS: &str = "MAN;60
+FACE;60
+MICRO;60
+OGRE;50
+DSO;30
+MACRO;60
+ACRE;50
+NEO;40
+FIGS;50
+MOD;40
+MEN
+FADE
+MAIL
+OGLE;20
+";
+
+fn blank_fixture() -> CrosswordData {
+    let mut crossword = fixture_crossword();
+
+    for crossword_box in crossword.crossword_data.iter_mut().flatten() {
+        if crossword_box.value != CrosswordBoxValue::Solid {
+            crossword_box.value = CrosswordBoxValue::Empty;
+        }
+    }
+
+    crossword
+}
+
+fn answers(crossword: &CrosswordData) -> Vec<String> {
+    crossword
+        .entries()
+        .into_iter()
+        .map(|entry| entry.answer)
+        .collect()
+}
+
+#[test]
+fn test_autofill() {
+    let words = WordList::parse(WORDS).unwrap();
+    let result = blank_fixture().autofill(&words, &AutofillOptions::default());
+
+    assert!(!result.stopped);
+    assert_eq!(result.fills.len(), 1);
+
+    let answers = answers(&result.fills[0].crossword);
+    assert_eq!(
+        answers,
+        vec!["MAN", "FACE", "MICRO", "OGRE", "DSO", "MACRO", "ACRE", "NEO", "FIGS", "MOD"]
+    );
+    assert_eq!(result.fills[0].score, 500);
+}
+
+#[test]
+fn test_autofill_locked_entry() {
+    let mut crossword = blank_fixture();
+    for (column, letter) in "FACE".chars().enumerate() {
+        crossword.crossword_data[1][column + 1].value = CrosswordBoxValue::Letter(letter);
+    }
+
+    // FACE is kept even though it isn't in the list
+    let words_without_face = WordList::parse(&WORDS.replace("FACE;60\n", "")).unwrap();
+    let options = AutofillOptions {
+        locked: vec![(4, Direction::Across)],
+        ..AutofillOptions::default()
+    };
+    let result = crossword.autofill(&words_without_face, &options);
+    assert_eq!(answers(&result.fills[0].crossword)[1], "FACE");
+
+    // Without the lock the entry has to be a listed word, and FACE isn't one
+    let result = crossword.autofill(&words_without_face, &AutofillOptions::default());
+    assert!(result.fills.is_empty());
+    assert!(!result.stopped);
+}
+
+#[test]
+fn test_autofill_top_fills_and_seed() {
+    let crossword = CrosswordData::from_grid(
+        2,
+        2,
+        vec![],
+        vec![],
+        vec![vec![CrosswordBoxValue::Empty; 2]; 2],
+    )
+    .unwrap();
+    let words = WordList::parse("AB\nCD\nAC\nBD\nBA\nDC\nCA\nDB\nAD;90\nBC;90").unwrap();
+    let options = AutofillOptions {
+        seed: 7,
+        results: 3,
+        ..AutofillOptions::default()
+    };
+
+    let result = crossword.autofill(&words, &options);
+    assert_eq!(result.fills.len(), 3);
+    assert!(result
+        .fills
+        .windows(2)
+        .all(|pair| pair[0].score >= pair[1].score));
+    for fill in &result.fills {
+        let answers = answers(&fill.crossword);
+        assert!(answers.iter().all(|answer| words.contains(answer)));
+        assert!((1..answers.len()).all(|index| !answers[..index].contains(&answers[index])));
+    }
+
+    assert_eq!(crossword.autofill(&words, &options), result);
+}
+
+#[test]
+fn test_autofill_keeps_searching_for_the_best_fill() {
+    let crossword = CrosswordData::from_grid(
+        2,
+        2,
+        vec![],
+        vec![],
+        vec![vec![CrosswordBoxValue::Empty; 2]; 2],
+    )
+    .unwrap();
+    // AB is tried first but only fits with the weakest words
+    let words = WordList::parse("AB;90\nAC;1\nBD;1\nCD;1\nEF;80\nEG;80\nFH;80\nGH;80").unwrap();
+    let options = AutofillOptions {
+        results: 2,
+        ..AutofillOptions::default()
+    };
+
+    let result = crossword.autofill(&words, &options);
+    assert!(!result.stopped);
+    let scores: Vec<u32> = result.fills.iter().map(|fill| fill.score).collect();
+    assert_eq!(scores, vec![320, 320]);
+}
+
+#[test]
+fn test_autofill_step_budget() {
+    let words = WordList::parse(WORDS).unwrap();
+    let options = AutofillOptions {
+        max_steps: Some(2),
+        ..AutofillOptions::default()
+    };
+
+    let result = blank_fixture().autofill(&words, &options);
+    assert!(result.stopped);
+    assert!(result.fills.is_empty());
+    assert_eq!(result.steps, 2);
+}
@@ -0,0 +1,196 @@
+use tgg::crossword::{Bars, CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData};
+use tgg::entry::Direction;
+use tgg::lint::{lint, LintConfig, LintFinding};
+use tgg::render::{RenderMode, TextRenderOptions};
+use tgg::svg::SvgOptions;
+use tgg::{Error, TggFile};
+
+/// A 4x4 grid without solid boxes, split by bars into rotationally symmetric entries:
+///
+/// ```text
+/// 1A 2B|3C 4D
+/// 5E_F  G  H_
+/// 6I J  K  7L
+/// 8M N| 9O P
+/// ```
+fn barred_boxes() -> Vec<Vec<CrosswordBox>> {
+    let letters = ["ABCD", "EFGH", "IJKL", "MNOP"];
+    let barred = [(0, 1, true), (3, 1, true), (1, 0, false), (1, 3, false)];
+
+    letters
+        .iter()
+        .enumerate()
+        .map(|(row, letters)| {
+            letters
+                .chars()
+                .enumerate()
+                .map(|(column, letter)| {
+                    let bars = Bars {
+                        right: barred.contains(&(row, column, true)),
+                        bottom: barred.contains(&(row, column, false)),
+                    };
+                    CrosswordBox::new(0, CrosswordBoxValue::Letter(letter))
+                        .unwrap()
+                        .with_bars(bars)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn clues(numbers: &[u8]) -> Vec<CrosswordClue> {
+    numbers
+        .iter()
+        .map(|&number| CrosswordClue::new(number, &format!("Clue {}", number)))
+        .collect()
+}
+
+fn barred_fixture() -> TggFile {
+    let mut unclued = CrosswordData::new(4, 4, Vec::new(), Vec::new(), barred_boxes()).unwrap();
+    unclued.auto_number().unwrap();
+
+    TggFile::custom_crossword(
+        "Barred",
+        "No blocks at all",
+        "Talon Games",
+        4,
+        4,
+        clues(&[1, 3, 5, 6, 8, 9]),
+        clues(&[1, 2, 3, 4, 6, 7]),
+        unclued.crossword_data,
+    )
+    .unwrap()
+}
+
+fn barred_crossword() -> CrosswordData {
+    barred_fixture().get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_bars_end_entries() {
+    let crossword = barred_crossword();
+    crossword.validate_numbering().unwrap();
+    crossword.validate_clues().unwrap();
+
+    let answers: Vec<(u8, Direction, String)> = crossword
+        .entries()
+        .into_iter()
+        .map(|entry| (entry.number, entry.direction, entry.answer))
+        .collect();
+    let expected = [
+        (1, Direction::Across, "AB"),
+        (3, Direction::Across, "CD"),
+        (5, Direction::Across, "EFGH"),
+        (6, Direction::Across, "IJKL"),
+        (8, Direction::Across, "MN"),
+        (9, Direction::Across, "OP"),
+        (1, Direction::Down, "AE"),
+        (2, Direction::Down, "BFJN"),
+        (3, Direction::Down, "CGKO"),
+        (4, Direction::Down, "DH"),
+        (6, Direction::Down, "IM"),
+        (7, Direction::Down, "LP"),
+    ];
+    let expected: Vec<(u8, Direction, String)> = expected
+        .iter()
+        .map(|(number, direction, answer)| (*number, *direction, answer.to_string()))
+        .collect();
+    assert_eq!(answers, expected);
+}
+
+#[test]
+fn test_bars_round_trip() {
+    let tgg_file = barred_fixture();
+
+    let bytes = tgg_file.to_bytes();
+    let gamedata = 17 + "Barred".len() + "No blocks at all".len() + "Talon Games".len() + 3 + 6;
+    assert_eq!(&bytes[gamedata..gamedata + 2], &[0, 5]);
+    let loaded = TggFile::from_bytes(bytes).unwrap();
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+
+    let (imported, warnings) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, warnings) = TggFile::from_jpz(&jpz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let text = tgg_file.to_text();
+    assert!(text.contains("2B|"));
+    assert!(text.contains("5E_"));
+    let imported = TggFile::from_text(&text).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    assert!(matches!(tgg_file.to_puz(), Err(Error::PuzBarsUnsupported)));
+}
+
+#[test]
+fn test_left_and_top_bars_import() {
+    // Bars on the left and top edge are stored on the neighbouring box
+    let ipuz = r#"{
+      "version": "http://ipuz.org/v2",
+      "kind": ["http://ipuz.org/crossword#1"],
+      "title": "Tiny",
+      "intro": "Bars on the outer edges",
+      "author": "Talon Games",
+      "dimensions": {"width": 2, "height": 2},
+      "puzzle": [
+        [1, {"cell": 0, "style": {"barred": "L"}}],
+        [2, {"cell": 0, "style": {"barred": "T"}}]
+      ],
+      "solution": [["A", "B"], ["C", "D"]],
+      "clues": {"Across": [[2, "Second"]], "Down": [[1, "First"]]}
+    }"#;
+
+    let (imported, warnings) = TggFile::from_ipuz(ipuz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let crossword = imported.get_crossword().unwrap();
+    assert!(crossword.crossword_data[0][0].bars.right);
+    assert!(crossword.crossword_data[0][1].bars.bottom);
+    assert_eq!(crossword.entries().len(), 2);
+}
+
+#[test]
+fn test_bars_rendering() {
+    let crossword = barred_crossword();
+
+    let svg = crossword.render_svg(&SvgOptions::default());
+    assert_eq!(svg.matches("<line").count(), 4);
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Solution,
+        clues: false,
+        ..TextRenderOptions::default()
+    });
+    assert!(text.contains("│ A │ B ┃ C │ D │"), "{}", text);
+    assert!(text.contains("├━━━┼───┼───┼━━━┤"), "{}", text);
+}
+
+#[test]
+fn test_bar_symmetry_lint() {
+    let mut crossword = barred_crossword();
+    let config = LintConfig::british_cryptic();
+    assert!(!lint(&crossword, &config).iter().any(|finding| matches!(
+        finding,
+        LintFinding::AsymmetricBar { .. }
+            | LintFinding::AsymmetricBlock { .. }
+            | LintFinding::DisconnectedBox { .. }
+    )));
+
+    crossword.crossword_data[3][1].bars.right = false;
+    let findings = lint(&crossword, &config);
+    assert!(findings.contains(&LintFinding::AsymmetricBar { row: 0, column: 1 }));
+}
+
+#[test]
+fn test_barred_crossword_data_round_trip() {
+    let crossword = barred_crossword();
+    assert_eq!(
+        CrosswordData::load(&crossword.to_bytes()).unwrap(),
+        crossword
+    );
+}
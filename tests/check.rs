@@ -0,0 +1,114 @@
+mod common;
+
+use common::fixture_crossword;
+use tgg::entry::Direction;
+use tgg::player::PlayerState;
+use tgg::Error;
+
+fn fill_across(state: &mut PlayerState, row: u8, column: u8, letters: &str) {
+    for (offset, letter) in letters.chars().enumerate() {
+        state
+            .set_guess(row, column + offset as u8, Some(&letter.to_string()), false)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_check() {
+    let crossword = fixture_crossword();
+    let mut state = PlayerState::new(&crossword);
+    fill_across(&mut state, 1, 1, "FICE");
+    fill_across(&mut state, 2, 0, "MI");
+
+    assert!(state.check_cell(&crossword, 1, 2).unwrap());
+    assert!(state.cells[1][2].checked);
+    assert!(!state.check_cell(&crossword, 1, 1).unwrap());
+    // Empty guesses and blocks aren't wrong
+    assert!(!state.check_cell(&crossword, 0, 0).unwrap());
+    assert!(!state.check_cell(&crossword, 3, 3).unwrap());
+    assert!(!state.cells[3][3].checked);
+
+    assert_eq!(
+        state.check_entry(&crossword, 4, Direction::Across).unwrap(),
+        vec![(1, 2)]
+    );
+    assert_eq!(
+        state.check_entry(&crossword, 4, Direction::Down).unwrap(),
+        vec![]
+    );
+
+    state.set_guess(4, 1, Some("Q"), false).unwrap();
+    assert_eq!(state.check_all(&crossword).unwrap(), vec![(1, 2), (4, 1)]);
+}
+
+#[test]
+fn test_reveal() {
+    let crossword = fixture_crossword();
+    let mut state = PlayerState::new(&crossword);
+    state.set_guess(0, 2, Some("X"), true).unwrap();
+
+    state.reveal_cell(&crossword, 0, 2).unwrap();
+    assert_eq!(state.cells[0][2].guess.as_deref(), Some("M"));
+    assert!(state.cells[0][2].revealed);
+    assert!(!state.cells[0][2].pencil);
+
+    state.reveal_entry(&crossword, 3, Direction::Down).unwrap();
+    let letters: Vec<Option<&str>> = (0..3)
+        .map(|row| state.cells[row][4].guess.as_deref())
+        .collect();
+    assert_eq!(letters, vec![Some("N"), Some("E"), Some("O")]);
+
+    // Only the revealed boxes get letters
+    assert_eq!(state.cells[1][1].guess, None);
+    assert_eq!(state.cells[0][0].guess, None);
+}
+
+#[test]
+fn test_is_solved() {
+    let crossword = fixture_crossword();
+    let mut state = PlayerState::new(&crossword);
+    assert!(!state.is_solved(&crossword).unwrap());
+
+    for entry in crossword.entries() {
+        state
+            .reveal_entry(&crossword, entry.number, entry.direction)
+            .unwrap();
+    }
+    assert!(state.is_solved(&crossword).unwrap());
+
+    state.set_guess(2, 2, Some("Z"), false).unwrap();
+    assert!(!state.is_solved(&crossword).unwrap());
+}
+
+#[test]
+fn test_check_errors() {
+    let crossword = fixture_crossword();
+    let mut state = PlayerState::new(&crossword);
+
+    assert!(matches!(
+        state.check_entry(&crossword, 2, Direction::Across),
+        Err(Error::EntryNotFound { number: 2 })
+    ));
+    assert!(matches!(
+        state.reveal_cell(&crossword, 0, 5),
+        Err(Error::BoxOutOfRange { row: 0, column: 5 })
+    ));
+
+    let mut other = crossword.clone();
+    other.vertical_clues[0].value = "Changed".to_string();
+    assert!(matches!(
+        state.check_all(&other),
+        Err(Error::PlayerStateMismatch)
+    ));
+
+    // A hand-built state with fewer boxes than the puzzle
+    state.cells.pop();
+    assert!(matches!(
+        state.check_all(&crossword),
+        Err(Error::BoxOutOfRange { row: 4, column: 0 })
+    ));
+    assert!(matches!(
+        state.reveal_cell(&crossword, 4, 0),
+        Err(Error::BoxOutOfRange { row: 4, column: 0 })
+    ));
+}
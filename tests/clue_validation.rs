@@ -0,0 +1,85 @@
+mod common;
+
+use common::fixture_crossword;
+use tgg::crossword::CrosswordClue;
+use tgg::Error;
+
+#[test]
+fn test_validate_clues() {
+    assert!(fixture_crossword().validate_clues().is_ok());
+}
+
+#[test]
+fn test_validate_clues_missing() {
+    let mut crossword = fixture_crossword();
+    crossword.horizontal_clues.remove(1);
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [Error::MissingHorizontalClue { number: 4 }]
+    ));
+
+    let mut crossword = fixture_crossword();
+    crossword.vertical_clues.pop();
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [Error::MissingVerticalClue { number: 5 }]
+    ));
+}
+
+#[test]
+fn test_validate_clues_without_entry() {
+    // Box 2 only starts a down entry
+    let mut crossword = fixture_crossword();
+    crossword
+        .horizontal_clues
+        .push(CrosswordClue::new(2, "Not an entry"));
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [Error::HorizontalClueWithoutEntry { number: 2 }]
+    ));
+
+    // Box 6 only starts an across entry
+    let mut crossword = fixture_crossword();
+    crossword.vertical_clues.push(CrosswordClue::new(6, "Nope"));
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [Error::VerticalClueWithoutEntry { number: 6 }]
+    ));
+}
+
+#[test]
+fn test_validate_clues_duplicate() {
+    let mut crossword = fixture_crossword();
+    crossword
+        .vertical_clues
+        .push(CrosswordClue::new(3, "Again"));
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [Error::RepeatedVerticalClue {
+            number: 3,
+            count: 2
+        }]
+    ));
+}
+
+#[test]
+fn test_validate_clues_reports_every_problem() {
+    let mut crossword = fixture_crossword();
+    crossword.horizontal_clues.remove(1);
+    crossword
+        .vertical_clues
+        .push(CrosswordClue::new(3, "Again"));
+    crossword.vertical_clues.push(CrosswordClue::new(6, "Nope"));
+
+    assert!(matches!(
+        crossword.validate_clues().unwrap_err().as_slice(),
+        [
+            Error::MissingHorizontalClue { number: 4 },
+            Error::RepeatedVerticalClue {
+                number: 3,
+                count: 2
+            },
+            Error::VerticalClueWithoutEntry { number: 6 }
+        ]
+    ));
+}
@@ -0,0 +1,70 @@
+//! Fixtures shared by the integration tests. Not every test file uses all of them.
+
+#![allow(dead_code)]
+
+use std::path::Path;
+use tgg::crossword::{CrosswordBoxValue, CrosswordData};
+use tgg::TggFile;
+
+/// The 5x5 crossword in `tests/crosswords`:
+///
+/// ```text
+/// # # M A N
+/// # F A C E
+/// M I C R O
+/// O G R E #
+/// D S O # #
+/// ```
+pub fn load_fixture() -> TggFile {
+    TggFile::load(Path::new("./tests/crosswords/crossword.tgg")).unwrap()
+}
+
+pub fn fixture_crossword() -> CrosswordData {
+    load_fixture().get_crossword().unwrap().clone()
+}
+
+/// A file with the fixture's metadata around another crossword.
+pub fn fixture_with(crossword: CrosswordData) -> TggFile {
+    let fixture = load_fixture();
+
+    TggFile::custom_crossword(
+        &fixture.get_title(),
+        &fixture.get_description(),
+        &fixture.get_author(),
+        crossword.width,
+        crossword.height,
+        crossword.horizontal_clues,
+        crossword.vertical_clues,
+        crossword.crossword_data,
+    )
+    .unwrap()
+}
+
+/// Box values from rows of `#` for solid boxes, `.` for empty boxes, `~` for void boxes
+/// and letters.
+pub fn grid_values(rows: &[&str]) -> Vec<Vec<CrosswordBoxValue>> {
+    rows.iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| match c {
+                    '#' => CrosswordBoxValue::Solid,
+                    '.' => CrosswordBoxValue::Empty,
+                    '~' => CrosswordBoxValue::Void,
+                    letter => CrosswordBoxValue::Letter(letter),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A numbered crossword without clues, from rows read by [`grid_values`].
+pub fn from_rows(rows: &[&str]) -> CrosswordData {
+    CrosswordData::from_grid(
+        rows[0].len() as u8,
+        rows.len() as u8,
+        Vec::new(),
+        Vec::new(),
+        grid_values(rows),
+    )
+    .unwrap()
+}
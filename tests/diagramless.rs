@@ -0,0 +1,197 @@
+mod common;
+
+use common::{fixture_crossword, fixture_with, load_fixture};
+use tgg::crossword::CrosswordData;
+use tgg::player::PlayerState;
+use tgg::render::{RenderMode, TextRenderOptions};
+use tgg::svg::SvgOptions;
+use tgg::{TggFile, Warning};
+
+/// The fixture as a diagramless crossword, blocks in the top left and bottom right:
+///
+/// ```text
+/// # # M A N
+/// # F A C E
+/// M I C R O
+/// O G R E #
+/// D S O # #
+/// ```
+fn diagramless_fixture() -> TggFile {
+    let crossword = fixture_crossword();
+    fixture_with(crossword).with_diagramless(true).unwrap()
+}
+
+fn diagramless_crossword() -> CrosswordData {
+    diagramless_fixture().get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_diagramless_needs_format_version_7() {
+    let fixture = load_fixture();
+    let gamedata = 17
+        + fixture.get_title().len()
+        + fixture.get_description().len()
+        + fixture.get_author().len()
+        + 3
+        + 6;
+
+    let tgg_file = diagramless_fixture();
+    let bytes = tgg_file.to_bytes();
+    assert_eq!(&bytes[gamedata..gamedata + 2], &[0, 7]);
+    let loaded = TggFile::from_bytes(bytes).unwrap();
+    assert!(loaded.get_crossword().unwrap().diagramless);
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+
+    // The puzzle flags come right before the two byte count of enumerations, which is
+    // the end of the game data
+    let mut bytes = diagramless_crossword().to_bytes();
+    let flags = bytes.len() - 3;
+    assert_eq!(&bytes[flags..], &[0b1, 0, 0]);
+    bytes[flags] = 0b10;
+    assert!(CrosswordData::load(&bytes).is_err());
+}
+
+#[test]
+fn test_diagramless_round_trip() {
+    let tgg_file = diagramless_fixture();
+
+    let ipuz = tgg_file.to_ipuz();
+    assert!(ipuz.contains("http://ipuz.org/crossword/diagramless#1"));
+    let (imported, warnings) = TggFile::from_ipuz(&ipuz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    // The puzzle type marks the file, and solvers start without blocks
+    let (puz, _) = tgg_file.to_puz().unwrap();
+    assert_eq!(&puz[0x30..0x32], &[0x01, 0x04]);
+    let player_grid = &puz[0x34 + 25..0x34 + 50];
+    assert!(player_grid.iter().all(|&byte| byte == b'-'));
+    let (imported, warnings) = TggFile::from_puz(&puz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    // Across Lite marks diagramless blocks with colons
+    let mut colons = puz.clone();
+    for byte in &mut colons[0x34..0x34 + 25] {
+        if *byte == b'.' {
+            *byte = b':';
+        }
+    }
+    let (imported, warnings) = TggFile::from_puz(&colons).unwrap();
+    assert!(!warnings
+        .iter()
+        .any(|warning| matches!(warning, Warning::UnsupportedCharacter { .. })));
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    // JPZ has no diagramless flag, the grid is kept as a regular crossword
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert_eq!(warnings, vec![Warning::JpzDiagramlessIgnored]);
+    let (imported, _) = TggFile::from_jpz(&jpz).unwrap();
+    let imported = imported.get_crossword().unwrap();
+    assert!(!imported.diagramless);
+    assert_eq!(
+        imported.crossword_data,
+        diagramless_crossword().crossword_data
+    );
+
+    let text = tgg_file.to_text();
+    assert!(text.contains("Diagramless: yes\n"));
+    let imported = TggFile::from_text(&text).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let regular = TggFile::from_text(&text.replace("Diagramless: yes", "Diagramless: no")).unwrap();
+    assert_eq!(
+        regular.get_crossword(),
+        tgg_file.with_diagramless(false).unwrap().get_crossword()
+    );
+}
+
+#[test]
+fn test_player_blocks() {
+    let crossword = diagramless_crossword();
+    let mut state = PlayerState::new(&crossword);
+    assert_eq!(state.to_bytes()[14], 1);
+
+    state.set_guess(0, 0, Some("X"), true).unwrap();
+    state.set_block(0, 0, true).unwrap();
+    assert!(state.cells[0][0].block);
+    assert_eq!(state.cells[0][0].guess, None);
+    assert!(!state.cells[0][0].pencil);
+
+    // Only saves with blocks need the newer version
+    let bytes = state.to_bytes();
+    assert_eq!(bytes[14], 2);
+    assert_eq!(PlayerState::from_bytes(&bytes).unwrap(), state);
+
+    state.set_guess(0, 0, Some("M"), false).unwrap();
+    assert!(!state.cells[0][0].block);
+}
+
+#[test]
+fn test_diagramless_checking() {
+    let crossword = diagramless_crossword();
+    let mut state = PlayerState::new(&crossword);
+
+    state.set_block(0, 0, true).unwrap();
+    state.set_block(0, 2, true).unwrap();
+    state.set_guess(0, 1, Some("A"), false).unwrap();
+    state.set_guess(0, 3, Some("A"), false).unwrap();
+
+    assert!(!state.check_cell(&crossword, 0, 0).unwrap());
+    assert!(state.cells[0][0].checked);
+    assert_eq!(state.check_all(&crossword).unwrap(), vec![(0, 1), (0, 2)]);
+
+    state.reveal_cell(&crossword, 0, 1).unwrap();
+    state.reveal_cell(&crossword, 0, 2).unwrap();
+    assert!(state.cells[0][1].block);
+    assert_eq!(state.cells[0][2].guess.as_deref(), Some("M"));
+
+    // Every letter is right, but the solver has to find the remaining blocks too
+    for (row, boxes) in crossword.crossword_data.iter().enumerate() {
+        for (column, crossword_box) in boxes.iter().enumerate() {
+            if let Some(content) = crossword_box.value.content() {
+                state
+                    .set_guess(row as u8, column as u8, Some(&content), false)
+                    .unwrap();
+            }
+        }
+    }
+    assert!(!state.is_solved(&crossword).unwrap());
+    for (row, column) in [(1, 0), (3, 4), (4, 3), (4, 4)] {
+        state.set_block(row, column, true).unwrap();
+    }
+    assert!(state.is_solved(&crossword).unwrap());
+}
+
+#[test]
+fn test_diagramless_rendering() {
+    let crossword = diagramless_crossword();
+
+    // A blank grid only shows the dimensions
+    let svg = crossword.render_svg(&SvgOptions::default());
+    assert_eq!(svg.matches("fill=\"#ffffff\"").count(), 25);
+    assert!(!svg.contains("<text"));
+
+    let solution = crossword.render_svg(&SvgOptions {
+        mode: RenderMode::Solution,
+        ..SvgOptions::default()
+    });
+    assert_eq!(solution.matches("fill=\"#000000\"").count(), 6);
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Blank,
+        clues: false,
+        ..TextRenderOptions::default()
+    });
+    assert!(text.lines().all(|line| !line.contains(char::is_numeric)));
+    assert!(!text.contains('█'), "{}", text);
+}
+
+#[test]
+fn test_diagramless_crossword_data_round_trip() {
+    let crossword = diagramless_crossword();
+    assert_eq!(
+        CrosswordData::load(&crossword.to_bytes()).unwrap(),
+        crossword
+    );
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::fixture_crossword;
+use tgg::entry::Direction;
+
+#[test]
+fn test_entries() {
+    let crossword = fixture_crossword();
+    let entries = crossword.entries();
+
+    let summary: Vec<(u8, Direction, &str)> = entries
+        .iter()
+        .map(|entry| (entry.number, entry.direction, entry.answer.as_str()))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            (1, Direction::Across, "MAN"),
+            (4, Direction::Across, "FACE"),
+            (5, Direction::Across, "MICRO"),
+            (6, Direction::Across, "OGRE"),
+            (7, Direction::Across, "DSO"),
+            (1, Direction::Down, "MACRO"),
+            (2, Direction::Down, "ACRE"),
+            (3, Direction::Down, "NEO"),
+            (4, Direction::Down, "FIGS"),
+            (5, Direction::Down, "MOD"),
+        ]
+    );
+
+    let figs = &entries[8];
+    assert_eq!(figs.start, (1, 1));
+    assert_eq!(figs.length, 4);
+    assert_eq!(figs.cells, vec![(1, 1), (2, 1), (3, 1), (4, 1)]);
+}
+
+#[test]
+fn test_entry_for_clue() {
+    let crossword = fixture_crossword();
+
+    let across = crossword
+        .entry_for_clue(&crossword.horizontal_clues[2])
+        .unwrap();
+    assert_eq!((across.number, across.direction), (5, Direction::Across));
+    assert_eq!(across.answer, "MICRO");
+
+    let down = crossword
+        .entry_for_clue(&crossword.vertical_clues[0])
+        .unwrap();
+    assert_eq!((down.number, down.direction), (1, Direction::Down));
+    assert_eq!(down.answer, "MACRO");
+}
+
+#[test]
+fn test_entries_at() {
+    let crossword = fixture_crossword();
+
+    let crossing: Vec<(u8, Direction)> = crossword
+        .entries_at(2, 2)
+        .iter()
+        .map(|entry| (entry.number, entry.direction))
+        .collect();
+    assert_eq!(crossing, vec![(5, Direction::Across), (1, Direction::Down)]);
+
+    assert!(crossword.entries_at(0, 0).is_empty());
+}
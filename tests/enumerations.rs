@@ -0,0 +1,184 @@
+mod common;
+
+use common::{fixture_crossword, fixture_with, load_fixture};
+use tgg::crossword::{CrosswordClue, CrosswordData, Enumeration, Separator};
+use tgg::entry::Direction;
+use tgg::render::TextRenderOptions;
+use tgg::svg::SvgOptions;
+use tgg::{Error, TggFile};
+
+/// The fixture with an enumeration on every clue. Longer answers are split in two
+/// hyphenated words, so the separators are covered too.
+fn enumerated_fixture() -> TggFile {
+    let mut crossword = fixture_crossword();
+    for entry in crossword.entries() {
+        let length = entry.answer.chars().count() as u8;
+        let enumeration = if length >= 4 {
+            Enumeration {
+                words: vec![2, length - 2],
+                separators: vec![Separator::Hyphen],
+            }
+        } else {
+            Enumeration {
+                words: vec![length],
+                separators: Vec::new(),
+            }
+        };
+        let clues = match entry.direction {
+            Direction::Across => &mut crossword.horizontal_clues,
+            Direction::Down => &mut crossword.vertical_clues,
+        };
+        for clue in clues.iter_mut().filter(|clue| clue.number == entry.number) {
+            clue.enumeration = Some(enumeration.clone());
+        }
+    }
+
+    fixture_with(crossword)
+}
+
+fn enumerated_crossword() -> CrosswordData {
+    enumerated_fixture().get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_parse_enumeration() {
+    let enumeration = Enumeration::parse("(5,3)").unwrap();
+    assert_eq!(enumeration.words, vec![5, 3]);
+    assert_eq!(enumeration.separators, vec![Separator::Space]);
+    assert_eq!(enumeration.length(), 8);
+    assert_eq!(enumeration.to_string(), "5,3");
+
+    assert_eq!(Enumeration::parse("5, 3").unwrap(), enumeration);
+    assert_eq!(Enumeration::parse("5 3").unwrap(), enumeration);
+    assert_eq!(Enumeration::parse("4-4").unwrap().to_string(), "4-4");
+    assert_eq!(Enumeration::parse("(3,2-4)").unwrap().to_string(), "3,2-4");
+
+    for invalid in ["(0)", "(5,,3)", "abc", "()", "(5,)", "(007)", "(4,05)"] {
+        assert!(
+            matches!(
+                Enumeration::parse(invalid),
+                Err(Error::InvalidEnumeration { .. })
+            ),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_clue_full_text() {
+    let clue = CrosswordClue::from_full_text(1, "Beaten eggs (8)");
+    assert_eq!(clue.value, "Beaten eggs");
+    assert_eq!(clue.enumeration, Some(Enumeration::parse("8").unwrap()));
+    assert_eq!(clue.full_text(), "Beaten eggs (8)");
+
+    // Parentheses that aren't an enumeration stay in the clue
+    let clue = CrosswordClue::from_full_text(1, "Greeting (informal)");
+    assert_eq!(clue.value, "Greeting (informal)");
+    assert_eq!(clue.enumeration, None);
+    assert_eq!(clue.full_text(), "Greeting (informal)");
+}
+
+#[test]
+fn test_enumerations_need_format_version_8() {
+    let fixture = load_fixture();
+    let gamedata = 17
+        + fixture.get_title().len()
+        + fixture.get_description().len()
+        + fixture.get_author().len()
+        + 3
+        + 6;
+    let bytes = fixture.to_bytes();
+    assert_ne!(&bytes[gamedata..gamedata + 2], &[0, 8]);
+
+    let tgg_file = enumerated_fixture();
+    let bytes = tgg_file.to_bytes();
+    assert_eq!(&bytes[gamedata..gamedata + 2], &[0, 8]);
+    let loaded = TggFile::from_bytes(bytes).unwrap();
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_enumeration_round_trip() {
+    let tgg_file = enumerated_fixture();
+
+    let (imported, warnings) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, warnings) = TggFile::from_jpz(&jpz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (puz, _) = tgg_file.to_puz().unwrap();
+    let (imported, warnings) = TggFile::from_puz(&puz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let imported = TggFile::from_text(&tgg_file.to_text()).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_validate_enumerations() {
+    let mut crossword = enumerated_crossword();
+    crossword.validate_enumerations().unwrap();
+
+    let number = crossword.horizontal_clues[0].number;
+    crossword.horizontal_clues[0].enumeration = Some(Enumeration::parse("9").unwrap());
+    assert!(matches!(
+        crossword.validate_enumerations(),
+        Err(Error::HorizontalEnumerationMismatch { number: n, found: 9, .. }) if n == number
+    ));
+
+    let mut crossword = enumerated_crossword();
+    crossword.vertical_clues[0].enumeration = Some(Enumeration::parse("1,1").unwrap());
+    assert!(matches!(
+        crossword.validate_enumerations(),
+        Err(Error::VerticalEnumerationMismatch { found: 2, .. })
+    ));
+}
+
+#[test]
+fn test_enumeration_rendering() {
+    let crossword = enumerated_crossword();
+    let expected = crossword.horizontal_clues[0].full_text();
+    assert!(expected.ends_with(')'));
+
+    let text = crossword.render_text(&TextRenderOptions::default());
+    assert!(text.contains(&expected), "{}", text);
+
+    let svg = crossword.render_svg(&SvgOptions {
+        clues: true,
+        ..SvgOptions::default()
+    });
+    let enumeration = crossword.horizontal_clues[0].enumeration.as_ref().unwrap();
+    assert!(svg.contains(&format!("({})", enumeration)), "{}", svg);
+}
+
+#[test]
+fn test_enumerated_crossword_data_round_trip() {
+    let crossword = enumerated_crossword();
+    assert_eq!(
+        CrosswordData::load(&crossword.to_bytes()).unwrap(),
+        crossword
+    );
+}
+
+#[test]
+fn test_puz_keeps_parentheses_that_dont_fit() {
+    let mut crossword = fixture_crossword();
+    crossword.horizontal_clues[0].value = "Apollo ___ (11)".to_string();
+    crossword.vertical_clues[0].value = "Agent (007)".to_string();
+    let tgg_file = fixture_with(crossword);
+
+    let (puz, _) = tgg_file.to_puz().unwrap();
+    let (imported, _) = TggFile::from_puz(&puz).unwrap();
+    let crossword = imported.get_crossword().unwrap();
+    assert_eq!(crossword.horizontal_clues[0].value, "Apollo ___ (11)");
+    assert_eq!(crossword.horizontal_clues[0].enumeration, None);
+    assert_eq!(crossword.vertical_clues[0].value, "Agent (007)");
+    assert_eq!(crossword.vertical_clues[0].enumeration, None);
+}
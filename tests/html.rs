@@ -0,0 +1,49 @@
+mod common;
+
+use common::load_fixture;
+use tgg::{Error, TggFile};
+
+#[test]
+fn test_html_export() {
+    let tgg_file = load_fixture();
+    let html = tgg_file.to_html().unwrap();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains(&format!("<h1>{}</h1>", tgg_file.get_title())));
+    assert!(html.contains("<style>"));
+    assert!(html.contains("id=\"puzzle-data\""));
+    assert!(html.contains("\"width\": 5"));
+    assert!(html.contains("To \\\"Alter\\\", informally"));
+    assert!(!html.contains("src="));
+    assert!(!html.contains("href="));
+}
+
+#[test]
+fn test_html_solution_is_obfuscated() {
+    let tgg_file = load_fixture();
+    let html = tgg_file.to_html().unwrap();
+
+    let start = html.find("\"solution\": \"").unwrap() + 13;
+    let solution = &html[start..start + 50];
+
+    assert!(solution.chars().all(|c| c.is_ascii_hexdigit()));
+    // Row two spells FACE, which must not show up in plain or hex form
+    assert!(!html.contains("FACE"));
+    assert!(!solution.contains("46414345"));
+}
+
+#[test]
+fn test_html_word_search() {
+    let tgg_file = TggFile::custom_word_search(
+        "Title",
+        "Description",
+        "Author",
+        2,
+        1,
+        vec!["AB".to_string()],
+        vec![vec!['A', 'B']],
+    )
+    .unwrap();
+
+    assert!(matches!(tgg_file.to_html(), Err(Error::NotACrossword)));
+}
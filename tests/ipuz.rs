@@ -0,0 +1,144 @@
+mod common;
+
+use common::load_fixture;
+use tgg::crossword::CrosswordBoxValue;
+use tgg::{Error, TggFile, Warning};
+
+const IPUZ: &str = r##"{
+  "version": "http://ipuz.org/v2",
+  "kind": ["http://ipuz.org/crossword#1"],
+  "title": "Small",
+  "author": "Talon Games",
+  "intro": "A tiny test puzzle",
+  "copyright": "2024 Talon Games",
+  "dimensions": {"width": 3, "height": 2},
+  "puzzle": [[1, 2, "#"], [3, 0, "#"]],
+  "solution": [["A", "B", "#"], ["C", "D", "#"]],
+  "clues": {
+    "Across": [[1, "First"], {"number": 3, "clue": "Second"}],
+    "Down": [[1, "Third"], [2, "Fourth"]],
+    "Diagonal": [[1, "Fifth"]]
+  }
+}"##;
+
+#[test]
+fn test_ipuz_import() {
+    let (tgg_file, warnings) = TggFile::from_ipuz(IPUZ).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let crossword = tgg_file.get_crossword().unwrap();
+    assert_eq!(tgg_file.get_title(), "Small");
+    assert_eq!(tgg_file.get_description(), "A tiny test puzzle");
+    assert_eq!(crossword.horizontal_clues[1].value, "Second");
+    assert_eq!(crossword.vertical_clues.len(), 2);
+    assert_eq!(crossword.crossword_data[1][0].number, 3);
+}
+
+#[test]
+fn test_ipuz_preserves_unknown_fields() {
+    let (tgg_file, _) = TggFile::from_ipuz(IPUZ).unwrap();
+
+    // The extension area has to survive saving the file
+    let reloaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    let ipuz = reloaded.to_ipuz();
+
+    assert!(ipuz.contains("\"copyright\": \"2024 Talon Games\""));
+    assert!(ipuz.contains("\"Diagonal\""));
+
+    let (round_trip, _) = TggFile::from_ipuz(&ipuz).unwrap();
+    assert_eq!(round_trip.get_crossword(), tgg_file.get_crossword());
+    assert_eq!(round_trip.get_extensions(), tgg_file.get_extensions());
+}
+
+#[test]
+fn test_ipuz_crossword_round_trip() {
+    let tgg_file = load_fixture();
+
+    let (imported, warnings) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+    assert!(imported.get_extensions().is_empty());
+}
+
+#[test]
+fn test_ipuz_word_search() {
+    let ipuz = r#"{
+        "version": "http://ipuz.org/v2",
+        "kind": ["http://ipuz.org/wordsearch#1"],
+        "title": "Pets",
+        "author": "Talon Games",
+        "intro": "Find the pets",
+        "dimensions": {"width": 3, "height": 3},
+        "puzzle": [["C", "A", "T"], ["X", "D", "O"], ["Y", "Z", "G"]],
+        "solution": ["cat", "dog"]
+    }"#;
+
+    let (tgg_file, warnings) = TggFile::from_ipuz(ipuz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let word_search = tgg_file.get_word_search().unwrap();
+    assert_eq!(word_search.words, vec!["CAT", "DOG"]);
+    assert_eq!(word_search.grid[1], vec!['X', 'D', 'O']);
+
+    let reloaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    let (round_trip, _) = TggFile::from_ipuz(&reloaded.to_ipuz()).unwrap();
+    assert_eq!(round_trip.get_word_search(), Some(word_search));
+}
+
+#[test]
+fn test_ipuz_omitted_cells_are_void() {
+    let ipuz = IPUZ.replace(r##"[3, 0, "#"]"##, "[3, 0, null]");
+
+    let (tgg_file, warnings) = TggFile::from_ipuz(&ipuz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let crossword = tgg_file.get_crossword().unwrap();
+    assert_eq!(
+        crossword.crossword_data[1][2].value,
+        CrosswordBoxValue::Void
+    );
+    assert_eq!(
+        crossword.crossword_data[0][2].value,
+        CrosswordBoxValue::Solid
+    );
+}
+
+#[test]
+fn test_ipuz_validation() {
+    let missing_puzzle = IPUZ.replace("\"puzzle\"", "\"grid\"");
+    assert!(matches!(
+        TggFile::from_ipuz(&missing_puzzle),
+        Err(Error::IpuzMissingField { field }) if field == "puzzle"
+    ));
+
+    let wrong_kind = IPUZ.replace("crossword#1", "sudoku#1");
+    assert!(matches!(
+        TggFile::from_ipuz(&wrong_kind),
+        Err(Error::UnsupportedIpuzKind { .. })
+    ));
+
+    assert!(matches!(
+        TggFile::from_ipuz("{\"version\": "),
+        Err(Error::JsonSyntax { .. })
+    ));
+
+    let nested = "[".repeat(200_000);
+    assert!(matches!(
+        TggFile::from_ipuz(&nested),
+        Err(Error::JsonSyntax { reason, .. }) if reason == "too deeply nested"
+    ));
+}
+
+#[test]
+fn test_ipuz_missing_metadata() {
+    let untitled = IPUZ.replace("\"title\": \"Small\",\n", "");
+    let (tgg_file, warnings) = TggFile::from_ipuz(&untitled).unwrap();
+    assert_eq!(tgg_file.get_title(), "Untitled");
+    assert!(warnings.contains(&Warning::MissingMetadata {
+        field: "title".to_string()
+    }));
+
+    // Placeholders keep the file loadable
+    let reloaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    assert_eq!(reloaded.get_title(), "Untitled");
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{fixture_crossword, fixture_with, load_fixture};
+use tgg::crossword::CrosswordClue;
+use tgg::{Error, TggFile, Warning};
+
+const JPZ: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<crossword-compiler-applet xmlns="http://crossword.info/xml/crossword-compiler-applet">
+  <rectangular-puzzle xmlns="http://crossword.info/xml/rectangular-puzzle">
+    <metadata>
+      <title>Small</title>
+      <creator>Talon Games</creator>
+      <copyright>&#169; 2024 Talon Games</copyright>
+    </metadata>
+    <crossword>
+      <grid width="3" height="2">
+        <grid-look numbering-scheme="normal"/>
+        <cell x="1" y="1" solution="A" number="1"/>
+        <cell x="2" y="1" solution="B" number="2"/>
+        <cell x="3" y="1" type="block"/>
+        <cell x="1" y="2" solution="C" number="3" background-shape="circle"/>
+        <cell x="2" y="2" solution="D"/>
+        <cell x="3" y="2" type="block"/>
+      </grid>
+      <word id="1" x="1-2" y="1"/>
+      <word id="2" x="1-2" y="2"/>
+      <word id="3" x="1" y="1-2"/>
+      <word id="4"><cells x="2" y="1"/><cells x="2" y="2"/></word>
+      <clues ordering="normal">
+        <title><b>Across</b></title>
+        <clue word="1" number="1">First &amp; best</clue>
+        <clue word="2" number="3">Second</clue>
+      </clues>
+      <clues ordering="normal">
+        <title><b>Down</b></title>
+        <clue word="3" number="1">Third</clue>
+        <clue word="4">Fourth</clue>
+      </clues>
+    </crossword>
+  </rectangular-puzzle>
+</crossword-compiler-applet>
+"#;
+
+#[test]
+fn test_jpz_import() {
+    let (tgg_file, warnings) = TggFile::from_jpz(JPZ).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    let crossword = tgg_file.get_crossword().unwrap();
+    assert!(crossword.crossword_data[1][0].style.circled);
+    assert_eq!(tgg_file.get_title(), "Small");
+    assert_eq!(tgg_file.get_description(), "\u{a9} 2024 Talon Games");
+    assert_eq!(crossword.horizontal_clues[0].value, "First & best");
+    assert_eq!(crossword.vertical_clues[1].number, 2);
+    assert_eq!(crossword.vertical_clues[1].value, "Fourth");
+}
+
+#[test]
+fn test_jpz_round_trip() {
+    let tgg_file = load_fixture();
+
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, warnings) = TggFile::from_jpz(&jpz).unwrap();
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_title(), tgg_file.get_title());
+    assert_eq!(imported.get_description(), tgg_file.get_description());
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_jpz_errors() {
+    assert!(matches!(
+        TggFile::from_jpz("<crossword-compiler><rectangular-puzzle>"),
+        Err(Error::XmlSyntax { .. })
+    ));
+
+    let nested = "<a>".repeat(200_000);
+    assert!(matches!(
+        TggFile::from_jpz(&nested),
+        Err(Error::XmlSyntax { reason, .. }) if reason == "too deeply nested"
+    ));
+
+    // Ranges are checked against the grid before they're expanded
+    let huge = JPZ.replace(
+        r#"<word id="1" x="1-2" y="1"/>"#,
+        r#"<word id="1" x="1-4000000000" y="1"/>"#,
+    );
+    assert!(matches!(
+        TggFile::from_jpz(&huge),
+        Err(Error::JpzInvalidAttribute { name }) if name == "x"
+    ));
+    let outside = JPZ.replace(r#"<cells x="2" y="2"/>"#, r#"<cells x="2" y="3"/>"#);
+    assert!(matches!(
+        TggFile::from_jpz(&outside),
+        Err(Error::JpzInvalidAttribute { name }) if name == "y"
+    ));
+
+    let acrostic = JPZ
+        .replace("<crossword>", "<acrostic>")
+        .replace("</crossword>", "</acrostic>");
+    assert!(matches!(
+        TggFile::from_jpz(&acrostic),
+        Err(Error::UnsupportedJpzPuzzle)
+    ));
+}
+
+#[test]
+fn test_jpz_missing_metadata() {
+    let untitled = JPZ.replace("<title>Small</title>", "");
+    let (tgg_file, warnings) = TggFile::from_jpz(&untitled).unwrap();
+    assert_eq!(tgg_file.get_title(), "Untitled");
+    assert!(warnings.contains(&Warning::MissingMetadata {
+        field: "title".to_string()
+    }));
+
+    // Placeholders keep the file loadable
+    let reloaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    assert_eq!(reloaded.get_title(), "Untitled");
+}
+
+#[test]
+fn test_jpz_clue_without_word() {
+    // 2 only starts a down word
+    let mut crossword = fixture_crossword();
+    crossword
+        .horizontal_clues
+        .push(CrosswordClue::new(2, "Nowhere"));
+
+    assert!(matches!(
+        fixture_with(crossword).to_jpz(),
+        Err(Error::HorizontalClueContainsInvalidNumber { number: 2 })
+    ));
+}
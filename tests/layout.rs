@@ -0,0 +1,95 @@
+use tgg::crossword::{CrosswordBoxValue, CrosswordData};
+use tgg::entry::Direction;
+use tgg::layout::LayoutOptions;
+use tgg::{Error, TggFile};
+
+const PAIRS: [(&str, &str); 8] = [
+    ("Photosynthesis", "How plants make food from light"),
+    ("Chlorophyll", "Green pigment in leaves"),
+    ("Stomata", "Pores on a leaf"),
+    ("Oxygen", "Gas released by plants"),
+    ("Roots", "They take up water"),
+    ("Carbon dioxide", "Gas taken in by plants"),
+    ("Sun", "Source of light energy"),
+    ("Glucose", "Sugar made by plants"),
+];
+
+#[test]
+fn test_generate_layout() {
+    let layout = CrosswordData::generate_layout(&PAIRS, &LayoutOptions::default()).unwrap();
+    let crossword = &layout.crossword;
+
+    assert!(crossword.width <= 15 && crossword.height <= 15);
+    assert!(crossword.validate_numbering().is_ok());
+    assert!(crossword.validate_clues().is_ok());
+
+    // Every placed answer is an entry with its own clue
+    let placed = PAIRS.len() - layout.unplaced.len();
+    assert!(placed >= 6, "only placed {}", placed);
+    assert_eq!(crossword.entries().len(), placed);
+    for entry in crossword.entries() {
+        let (_, clue) = PAIRS
+            .iter()
+            .find(|(answer, _)| answer.replace(' ', "").to_uppercase() == entry.answer)
+            .unwrap();
+        let clues = match entry.direction {
+            Direction::Across => &crossword.horizontal_clues,
+            Direction::Down => &crossword.vertical_clues,
+        };
+        assert!(clues
+            .iter()
+            .any(|c| c.number == entry.number && c.value == *clue));
+    }
+
+    // The result can be saved like any other crossword
+    let tgg_file = TggFile::custom_crossword(
+        "Plants",
+        "Biology",
+        "Teacher",
+        crossword.width,
+        crossword.height,
+        crossword.horizontal_clues.clone(),
+        crossword.vertical_clues.clone(),
+        crossword.crossword_data.clone(),
+    )
+    .unwrap();
+    assert!(TggFile::from_bytes(tgg_file.to_bytes()).is_ok());
+}
+
+#[test]
+fn test_generate_layout_is_seeded() {
+    let options = LayoutOptions {
+        seed: 42,
+        ..LayoutOptions::default()
+    };
+
+    assert_eq!(
+        CrosswordData::generate_layout(&PAIRS, &options).unwrap(),
+        CrosswordData::generate_layout(&PAIRS, &options).unwrap()
+    );
+}
+
+#[test]
+fn test_generate_layout_size_limit() {
+    let options = LayoutOptions {
+        max_width: 8,
+        max_height: 8,
+        ..LayoutOptions::default()
+    };
+    let layout = CrosswordData::generate_layout(&PAIRS, &options).unwrap();
+
+    assert!(layout.crossword.width <= 8 && layout.crossword.height <= 8);
+    assert!(layout.unplaced.contains(&"Photosynthesis".to_string()));
+    assert!(layout.unplaced.contains(&"Carbon dioxide".to_string()));
+    assert!(layout
+        .crossword
+        .crossword_data
+        .iter()
+        .flatten()
+        .all(|cell| cell.value != CrosswordBoxValue::Empty));
+
+    assert!(matches!(
+        CrosswordData::generate_layout(&[("Ümlaut", "Nope")], &options),
+        Err(Error::NoWordsPlaced)
+    ));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{fixture_crossword, from_rows};
+use tgg::crossword::CrosswordBoxValue;
+use tgg::entry::Direction;
+use tgg::lint::{lint, LintConfig, LintFinding};
+
+#[test]
+fn test_lint_clean_grid() {
+    let crossword = fixture_crossword();
+
+    assert!(lint(&crossword, &LintConfig::american_15x15()).is_empty());
+    assert!(lint(&crossword, &LintConfig::british_cryptic()).is_empty());
+}
+
+#[test]
+fn test_lint_symmetry_and_short_entries() {
+    let mut crossword = fixture_crossword();
+    crossword.crossword_data[0][2].value = CrosswordBoxValue::Solid;
+
+    let findings = lint(&crossword, &LintConfig::american_15x15());
+
+    assert!(findings.contains(&LintFinding::AsymmetricBlock { row: 0, column: 2 }));
+    assert!(findings.contains(&LintFinding::ShortEntry {
+        row: 0,
+        column: 3,
+        direction: Direction::Across,
+        length: 2
+    }));
+}
+
+#[test]
+fn test_lint_connectivity() {
+    let crossword = from_rows(&["AB#", "###", "#CD"]);
+    let config = LintConfig {
+        connectivity: true,
+        ..LintConfig::default()
+    };
+
+    assert_eq!(
+        lint(&crossword, &config),
+        vec![
+            LintFinding::DisconnectedBox { row: 2, column: 1 },
+            LintFinding::DisconnectedBox { row: 2, column: 2 },
+        ]
+    );
+}
+
+#[test]
+fn test_lint_checking_profiles() {
+    let crossword = from_rows(&["ABCDE", "F#G#H", "IJKLM", "N#O#P", "QRSTU"]);
+
+    assert!(lint(&crossword, &LintConfig::british_cryptic()).is_empty());
+
+    let findings = lint(&crossword, &LintConfig::american_15x15());
+    assert_eq!(findings.len(), 12);
+    assert_eq!(findings[0], LintFinding::UncheckedBox { row: 0, column: 1 });
+
+    let crossword = from_rows(&["ABCD", "E##F", "GHIJ"]);
+    assert_eq!(
+        lint(&crossword, &LintConfig::british_cryptic()),
+        vec![
+            LintFinding::ConsecutiveUncheckedBoxes {
+                row: 0,
+                column: 2,
+                direction: Direction::Across
+            },
+            LintFinding::ConsecutiveUncheckedBoxes {
+                row: 2,
+                column: 2,
+                direction: Direction::Across
+            },
+        ]
+    );
+}
@@ -1,4 +1,5 @@
 use std::path::Path;
+use tgg::crossword::CrosswordData;
 use tgg::TggFile;
 
 #[test]
@@ -13,3 +14,14 @@ fn test_load_crossword() {
         tgg_file.unwrap_err()
     );
 }
+
+#[test]
+fn test_crossword_data_round_trip() {
+    let tgg_file = TggFile::load(Path::new("./tests/crosswords/crossword.tgg")).unwrap();
+    let crossword = tgg_file.get_crossword().unwrap();
+
+    assert_eq!(
+        &CrosswordData::load(&crossword.to_bytes()).unwrap(),
+        crossword
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::{fixture_crossword, grid_values};
+use tgg::crossword::{CrosswordClue, CrosswordData};
+use tgg::Error;
+
+#[test]
+fn test_from_grid_numbers_entries() {
+    let crossword = CrosswordData::from_grid(
+        3,
+        3,
+        vec![
+            CrosswordClue::new(1, "Feline"),
+            CrosswordClue::new(4, "Exist"),
+        ],
+        vec![CrosswordClue::new(2, "Consumed")],
+        grid_values(&["CAT", "A.O", "BEE"]),
+    )
+    .unwrap();
+
+    let numbers: Vec<Vec<u8>> = crossword
+        .crossword_data
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.number).collect())
+        .collect();
+
+    assert_eq!(numbers, vec![vec![1, 2, 3], vec![4, 0, 0], vec![5, 0, 0]]);
+    assert!(crossword.validate_numbering().is_ok());
+}
+
+#[test]
+fn test_from_grid_rejects_clue_without_number() {
+    let result = CrosswordData::from_grid(
+        2,
+        2,
+        vec![CrosswordClue::new(5, "Nope")],
+        vec![],
+        grid_values(&["AB", "CD"]),
+    );
+
+    assert!(matches!(
+        result,
+        Err(Error::HorizontalClueContainsInvalidNumber { number: 5 })
+    ));
+}
+
+#[test]
+fn test_validate_numbering() {
+    let mut crossword = fixture_crossword();
+    assert!(crossword.validate_numbering().is_ok());
+
+    // A number on a box that starts no entry
+    crossword.crossword_data[2][2].number = 9;
+    assert!(matches!(
+        crossword.validate_numbering(),
+        Err(Error::NumberMismatch {
+            row: 2,
+            column: 2,
+            expected: 0,
+            found: 9
+        })
+    ));
+
+    // Numbers out of order
+    crossword.auto_number().unwrap();
+    crossword.crossword_data[0][3].number = 3;
+    crossword.crossword_data[0][4].number = 2;
+    assert!(matches!(
+        crossword.validate_numbering(),
+        Err(Error::NumberMismatch {
+            row: 0,
+            column: 3,
+            expected: 2,
+            found: 3
+        })
+    ));
+
+    crossword.auto_number().unwrap();
+    assert_eq!(crossword, fixture_crossword());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::load_fixture;
+use tgg::pdf::{PageSize, PdfOptions};
+use tgg::{Error, TggFile};
+
+fn page_count(pdf: &str) -> usize {
+    pdf.matches("/Type /Page ").count()
+}
+
+#[test]
+fn test_pdf_with_answer_key() {
+    let tgg_file = load_fixture();
+    let bytes = tgg_file.to_pdf(&PdfOptions::default()).unwrap();
+    let pdf = String::from_utf8_lossy(&bytes);
+
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.trim_end().ends_with("%%EOF"));
+    assert_eq!(page_count(&pdf), 2);
+    assert!(pdf.contains("/MediaBox [0 0 612 792]"));
+    assert!(pdf.contains(&format!("({}) Tj", tgg_file.get_title())));
+    assert!(pdf.contains("(Across) Tj"));
+    assert!(pdf.contains("(Down) Tj"));
+    assert!(pdf.contains("(Answer Key) Tj"));
+    assert!(pdf.contains("(M) Tj"));
+
+    // Every xref entry points at the object it lists
+    let xref = pdf.rfind("xref\n").unwrap();
+    for (index, entry) in pdf[xref..]
+        .lines()
+        .skip(3)
+        .take_while(|line| line.ends_with(" n "))
+        .enumerate()
+    {
+        let offset: usize = entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+    }
+}
+
+#[test]
+fn test_pdf_without_answer_key() {
+    let tgg_file = load_fixture();
+    let options = PdfOptions {
+        page_size: PageSize::A4,
+        answer_key: false,
+    };
+    let bytes = tgg_file.to_pdf(&options).unwrap();
+    let pdf = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(page_count(&pdf), 1);
+    assert!(pdf.contains("/MediaBox [0 0 595 842]"));
+    assert!(!pdf.contains("(Answer Key) Tj"));
+    assert!(!pdf.contains("(M) Tj"));
+}
+
+#[test]
+fn test_pdf_word_search() {
+    let tgg_file = TggFile::custom_word_search(
+        "Title",
+        "Description",
+        "Author",
+        2,
+        1,
+        vec!["AB".to_string()],
+        vec![vec!['A', 'B']],
+    )
+    .unwrap();
+
+    assert!(matches!(
+        tgg_file.to_pdf(&PdfOptions::default()),
+        Err(Error::NotACrossword)
+    ));
+}
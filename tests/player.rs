@@ -0,0 +1,116 @@
+mod common;
+
+use common::fixture_crossword;
+use tgg::player::{PlayerCell, PlayerState};
+use tgg::Error;
+
+#[test]
+fn test_player_state_round_trip() {
+    let crossword = fixture_crossword();
+    let mut state = PlayerState::new(&crossword);
+    state.set_guess(0, 2, Some("M"), false).unwrap();
+    state.set_guess(1, 1, Some("F"), true).unwrap();
+    state.cells[2][0].checked = true;
+    state.cells[2][1].revealed = true;
+    state.elapsed_seconds = 754;
+
+    let bytes = state.to_bytes();
+    assert_eq!(&bytes[0..14], b"TalonGamesSave");
+    assert_eq!(bytes.len(), 17 + 14 + 5 * 5 * 2 + 2);
+    assert_eq!(&bytes[17..25], &crossword.fingerprint().to_be_bytes());
+    assert_eq!(&bytes[25..29], &754u32.to_be_bytes());
+
+    let loaded = PlayerState::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded, state);
+    assert!(loaded.matches(&crossword));
+    assert_eq!(
+        loaded.cell(1, 1),
+        Some(&PlayerCell {
+            guess: Some("F".to_string()),
+            pencil: true,
+            checked: false,
+            revealed: false,
+            block: false,
+        })
+    );
+}
+
+#[test]
+fn test_fingerprint_identifies_puzzle() {
+    let crossword = fixture_crossword();
+    let state = PlayerState::new(&crossword);
+    assert_eq!(crossword.fingerprint(), fixture_crossword().fingerprint());
+
+    let mut changed = crossword.clone();
+    changed.horizontal_clues[0].value = "Something else".to_string();
+    assert_ne!(changed.fingerprint(), crossword.fingerprint());
+    assert!(!state.matches(&changed));
+}
+
+#[test]
+fn test_set_guess() {
+    let mut state = PlayerState::new(&fixture_crossword());
+    state.cells[0][2].checked = true;
+
+    state.set_guess(0, 2, Some("X"), false).unwrap();
+    assert!(!state.cells[0][2].checked);
+
+    state.set_guess(0, 2, None, true).unwrap();
+    assert_eq!(state.cells[0][2], PlayerCell::default());
+
+    assert!(matches!(
+        state.set_guess(0, 2, Some("x"), false),
+        Err(Error::NonUppercaseCharacter)
+    ));
+    assert!(matches!(
+        state.set_guess(5, 0, Some("X"), false),
+        Err(Error::BoxOutOfRange { row: 5, column: 0 })
+    ));
+}
+
+#[test]
+fn test_corrupt_player_state() {
+    let mut state = PlayerState::new(&fixture_crossword());
+    state.set_guess(0, 2, Some("M"), false).unwrap();
+    let bytes = state.to_bytes();
+
+    let mut corrupt = bytes.clone();
+    corrupt[40] ^= 0x01;
+    assert!(matches!(
+        PlayerState::from_bytes(&corrupt),
+        Err(Error::HeaderChecksumMismatch { .. })
+    ));
+
+    let mut truncated = bytes[..bytes.len() - 4].to_vec();
+    truncated.extend(&bytes[bytes.len() - 2..]);
+    assert!(PlayerState::from_bytes(&truncated).is_err());
+
+    let mut wrong_id = bytes.clone();
+    wrong_id[0] = b'X';
+    assert!(matches!(
+        PlayerState::from_bytes(&wrong_id),
+        Err(Error::InvalidID)
+    ));
+}
+
+#[test]
+fn test_invalid_rebus_guess() {
+    let mut state = PlayerState::new(&fixture_crossword());
+    state.set_guess(2, 2, Some("STAR"), false).unwrap();
+    let bytes = state.to_bytes();
+
+    // Lowercase the rebus and write the checksums of the changed body
+    let mut corrupt = bytes.clone();
+    let rebus = corrupt.len() - 2 - 5;
+    corrupt[rebus..rebus + 4].make_ascii_lowercase();
+    let body = &corrupt[17..corrupt.len() - 2];
+    let checksum = body.iter().map(|&byte| byte as u32).sum::<u32>() as u16;
+    let length = corrupt.len();
+    corrupt[15..17].copy_from_slice(&checksum.to_le_bytes());
+    corrupt[length - 2..].copy_from_slice(&checksum.to_le_bytes());
+
+    assert!(matches!(
+        PlayerState::from_bytes(&corrupt),
+        Err(Error::NonUppercaseCharacter)
+    ));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::load_fixture;
+use tgg::{Error, TggFile, Warning};
+
+#[test]
+fn test_puz_round_trip() {
+    let tgg_file = load_fixture();
+
+    let (bytes, warnings) = tgg_file.to_puz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(&bytes[0x02..0x0E], b"ACROSS&DOWN\0");
+
+    let (imported, warnings) = TggFile::from_puz(&bytes).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_title(), tgg_file.get_title());
+    assert_eq!(imported.get_author(), tgg_file.get_author());
+    assert_eq!(imported.get_description(), tgg_file.get_description());
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_puz_circles_import() {
+    let (mut bytes, _) = load_fixture().to_puz().unwrap();
+
+    let mut markup = vec![0u8; 25];
+    markup[7] = 0x80;
+    bytes.extend(b"GEXT");
+    bytes.extend((markup.len() as u16).to_le_bytes());
+    bytes.extend(0u16.to_le_bytes());
+    bytes.extend(&markup);
+    bytes.push(0);
+
+    let (imported, warnings) = TggFile::from_puz(&bytes).unwrap();
+    let crossword = imported.get_crossword().unwrap();
+    assert!(crossword.crossword_data[1][2].style.circled);
+    assert!(!crossword.crossword_data[1][3].style.circled);
+    assert!(warnings.contains(&Warning::PuzChecksumMismatch {
+        section: "GEXT".to_string()
+    }));
+}
+
+#[test]
+fn test_puz_invalid_magic() {
+    assert!(matches!(
+        TggFile::from_puz(&[0; 64]),
+        Err(Error::InvalidPuzMagic)
+    ));
+}
+
+#[test]
+fn test_puz_missing_metadata() {
+    let tgg_file = load_fixture();
+    let (bytes, _) = tgg_file.to_puz().unwrap();
+
+    // Drop the title, the first string after the two grids
+    let strings = 0x34 + 2 * 25;
+    let title_end = strings + tgg_file.get_title().len();
+    let mut untitled = bytes[..strings].to_vec();
+    untitled.extend(&bytes[title_end..]);
+
+    let (imported, warnings) = TggFile::from_puz(&untitled).unwrap();
+    assert_eq!(imported.get_title(), "Untitled");
+    assert!(warnings.contains(&Warning::MissingMetadata {
+        field: "title".to_string()
+    }));
+
+    // Placeholders keep the file loadable
+    let reloaded = TggFile::from_bytes(imported.to_bytes()).unwrap();
+    assert_eq!(reloaded.get_title(), "Untitled");
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::{fixture_crossword, fixture_with, from_rows, load_fixture};
+use tgg::crossword::{CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData};
+use tgg::entry::Direction;
+use tgg::player::PlayerState;
+use tgg::render::{Charset, RenderMode, TextRenderOptions};
+use tgg::svg::SvgOptions;
+use tgg::{Error, TggFile};
+
+/// The fixture with STAR in the box where MICRO and MACRO cross.
+fn rebus_fixture() -> TggFile {
+    let mut crossword = fixture_crossword();
+    crossword.crossword_data[2][2].value = CrosswordBoxValue::Rebus("STAR".to_string());
+
+    fixture_with(crossword)
+}
+
+fn rebus_crossword() -> CrosswordData {
+    rebus_fixture().get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_rebus_round_trip() {
+    let tgg_file = rebus_fixture();
+    let bytes = tgg_file.to_bytes();
+
+    let loaded = TggFile::from_bytes(bytes).unwrap();
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+
+    let (puz, warnings) = tgg_file.to_puz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, warnings) = TggFile::from_puz(&puz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (imported, _) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, _) = TggFile::from_jpz(&jpz).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let text = tgg_file.to_text();
+    assert!(text.contains("[STAR]"));
+    let imported = TggFile::from_text(&text).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_too_many_rebuses_for_puz() {
+    // A 16x16 grid with a different rebus in each of its 256 boxes
+    let mut crossword = from_rows(&["AAAAAAAAAAAAAAAA"; 16]);
+    crossword.horizontal_clues = std::iter::once(1)
+        .chain(17..32)
+        .map(|number| CrosswordClue::new(number, "Across"))
+        .collect();
+    crossword.vertical_clues = (1..17)
+        .map(|number| CrosswordClue::new(number, "Down"))
+        .collect();
+    for (index, cell) in crossword.crossword_data.iter_mut().flatten().enumerate() {
+        let rebus = [b'A' + (index / 26) as u8, b'A' + (index % 26) as u8];
+        cell.value = CrosswordBoxValue::Rebus(String::from_utf8(rebus.to_vec()).unwrap());
+    }
+
+    assert!(matches!(
+        fixture_with(crossword).to_puz(),
+        Err(Error::PuzTooManyRebuses)
+    ));
+}
+
+#[test]
+fn test_rebus_needs_format_version_3() {
+    // Without a rebus the game data is still written as version 1
+    let plain = load_fixture().to_bytes();
+    let rebus = rebus_fixture().to_bytes();
+    let title_length = load_fixture().get_title().len();
+    let description_length = load_fixture().get_description().len();
+    let author_length = load_fixture().get_author().len();
+    let gamedata = 17 + title_length + description_length + author_length + 3 + 6;
+
+    assert_eq!(plain[gamedata], 5);
+    assert_eq!(&rebus[gamedata..gamedata + 2], &[0, 3]);
+}
+
+#[test]
+fn test_invalid_rebus() {
+    let rebus = |content: &str| CrosswordBox::new(0, CrosswordBoxValue::Rebus(content.to_string()));
+
+    assert!(rebus("&").is_ok());
+    assert!(matches!(rebus(""), Err(Error::RebusIsEmpty)));
+    assert!(matches!(
+        rebus("ST AR"),
+        Err(Error::InvalidRebusCharacter { found: ' ' })
+    ));
+    assert!(matches!(rebus("Star"), Err(Error::NonUppercaseCharacter)));
+    assert!(matches!(rebus("STÄR"), Err(Error::NonAsciiCharacter)));
+}
+
+#[test]
+fn test_rebus_entries_and_rendering() {
+    let crossword = rebus_crossword();
+    let entries = crossword.entries();
+
+    let micro = entries
+        .iter()
+        .find(|entry| entry.number == 5 && entry.direction == Direction::Across)
+        .unwrap();
+    assert_eq!(micro.answer, "MISTARRO");
+    assert_eq!(micro.length, 5);
+
+    let svg = crossword.render_svg(&SvgOptions {
+        mode: RenderMode::Solution,
+        ..SvgOptions::default()
+    });
+    assert!(svg.contains(">STAR</text>"));
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Solution,
+        ..TextRenderOptions::default()
+    });
+    assert!(text.contains("ST…"));
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Solution,
+        charset: Charset::Ascii,
+        ..TextRenderOptions::default()
+    });
+    assert!(text.contains("ST~"));
+    assert!(text.is_ascii());
+}
+
+#[test]
+fn test_rebus_check_and_reveal() {
+    let crossword = rebus_crossword();
+    let mut state = PlayerState::new(&crossword);
+
+    state.set_guess(2, 2, Some("S"), false).unwrap();
+    assert!(state.check_cell(&crossword, 2, 2).unwrap());
+    state.set_guess(2, 2, Some("STAR"), false).unwrap();
+    assert!(!state.check_cell(&crossword, 2, 2).unwrap());
+
+    state.reveal_entry(&crossword, 1, Direction::Down).unwrap();
+    assert_eq!(state.cells[2][2].guess.as_deref(), Some("STAR"));
+
+    let loaded = PlayerState::from_bytes(&state.to_bytes()).unwrap();
+    assert_eq!(loaded, state);
+}
@@ -0,0 +1,78 @@
+use tgg::crossword::{CrosswordBox, CrosswordBoxValue, CrosswordClue, CrosswordData};
+use tgg::render::{Charset, RenderMode, TextRenderOptions};
+
+fn small_crossword() -> CrosswordData {
+    let letter = |number, letter| CrosswordBox::new(number, CrosswordBoxValue::Letter(letter));
+
+    CrosswordData::new(
+        2,
+        2,
+        vec![CrosswordClue::new(1, "First")],
+        vec![CrosswordClue::new(1, "Second")],
+        vec![
+            vec![letter(1, 'A').unwrap(), letter(0, 'B').unwrap()],
+            vec![
+                letter(0, 'C').unwrap(),
+                CrosswordBox::new(0, CrosswordBoxValue::Solid).unwrap(),
+            ],
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_render_text_solution() {
+    let rendered = small_crossword().render_text(&TextRenderOptions::default());
+
+    assert_eq!(
+        rendered,
+        "┌───┬───┐
+│1  │   │
+│ A │ B │
+├───┼───┤
+│   │███│
+│ C │███│
+└───┴───┘
+
+Across
+1. First
+
+Down
+1. Second
+"
+    );
+}
+
+#[test]
+fn test_render_text_blank_ascii() {
+    let options = TextRenderOptions {
+        mode: RenderMode::Blank,
+        charset: Charset::Ascii,
+        numbers: false,
+        color: false,
+        clues: false,
+    };
+
+    assert_eq!(
+        small_crossword().render_text(&options),
+        "+---+---+
+|   |   |
++---+---+
+|   |###|
++---+---+
+"
+    );
+}
+
+#[test]
+fn test_render_text_color() {
+    let options = TextRenderOptions {
+        color: true,
+        clues: false,
+        ..TextRenderOptions::default()
+    };
+
+    let rendered = small_crossword().render_text(&options);
+    assert!(rendered.contains("\x1b[1m A \x1b[0m"));
+    assert!(rendered.contains("\x1b[7m   \x1b[0m"));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{fixture_crossword, from_rows};
+use tgg::solver::SolverOptions;
+use tgg::word_list::WordList;
+
+const WORDS: &str = "MAN\nFACE\nMICRO\nOGRE\nDSO\nMACRO\nACRE\nNEO\nFIGS\nMOD\nMEN\nFADE\nOGLE\n";
+
+#[test]
+fn test_unique_solution() {
+    let crossword = from_rows(&["CAT", "ORE"]);
+    let words = WordList::parse("CAT\nORE\nCO\nAR\nTE\nCOT\nARE\nTO").unwrap();
+    let result = crossword.solve(&words, &SolverOptions::default());
+
+    assert!(result.is_unique());
+    assert_eq!(result.solutions, vec![crossword]);
+    assert_eq!(result.intended, Some(0));
+    assert!(result.ambiguous_boxes.is_empty());
+}
+
+#[test]
+fn test_transposed_solution() {
+    // The grid is symmetric about its diagonal, so the down answers also fit across
+    let words = WordList::parse(WORDS).unwrap();
+    let result = fixture_crossword().solve(&words, &SolverOptions::default());
+
+    assert!(!result.is_unique());
+    assert!(!result.stopped);
+    assert_eq!(result.solutions.len(), 2);
+    assert_eq!(
+        result.solutions[result.intended.unwrap()],
+        fixture_crossword()
+    );
+    assert!(result.ambiguous_boxes.contains(&(0, 3)));
+    assert!(!result.ambiguous_boxes.contains(&(0, 2)));
+}
+
+#[test]
+fn test_ambiguous_solution() {
+    // The grid can be read either way round
+    let crossword = from_rows(&["AB", "CD"]);
+    let words = WordList::parse("AB\nCD\nAC\nBD").unwrap();
+    let result = crossword.solve(&words, &SolverOptions::default());
+
+    assert!(!result.is_unique());
+    assert!(!result.stopped);
+    assert_eq!(result.solutions.len(), 2);
+    assert!(result.intended.is_some());
+    assert_eq!(result.ambiguous_boxes, vec![(0, 1), (1, 0)]);
+}
+
+#[test]
+fn test_intended_solution_not_in_word_list() {
+    let words = WordList::parse("AB\nCD\nAC\nBE").unwrap();
+    let result = from_rows(&["AB", "CD"]).solve(&words, &SolverOptions::default());
+
+    assert!(result.solutions.is_empty());
+    assert_eq!(result.intended, None);
+    assert!(!result.is_unique());
+}
+
+#[test]
+fn test_solution_limit() {
+    let crossword = from_rows(&["AB", "CD"]);
+    let words = WordList::parse("AB\nCD\nAC\nBD").unwrap();
+    let options = SolverOptions {
+        max_solutions: 1,
+        ..SolverOptions::default()
+    };
+
+    let result = crossword.solve(&words, &options);
+    assert_eq!(result.solutions.len(), 1);
+    assert!(result.stopped);
+    assert!(!result.is_unique());
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::{fixture_crossword, fixture_with, load_fixture};
+use tgg::crossword::{CellStyle, CrosswordData};
+use tgg::render::{RenderMode, TextRenderOptions};
+use tgg::svg::SvgOptions;
+use tgg::{TggFile, Warning};
+
+/// The fixture with MICRO circled, its last box shaded and the first box of OGRE bold.
+fn styled_fixture(shade: bool, bold: bool) -> TggFile {
+    let mut crossword = fixture_crossword();
+    for crossword_box in &mut crossword.crossword_data[2] {
+        crossword_box.style.circled = true;
+    }
+    if shade {
+        crossword.crossword_data[2][4].style.shade = Some([0xff, 0xcc, 0x00]);
+    }
+    if bold {
+        crossword.crossword_data[3][0].style.bold_border = true;
+    }
+
+    fixture_with(crossword)
+}
+
+fn styled_crossword() -> CrosswordData {
+    styled_fixture(true, true).get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_style_round_trip() {
+    let tgg_file = styled_fixture(true, true);
+
+    let loaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+
+    let (imported, warnings) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let text = tgg_file.to_text();
+    assert!(text.contains("5M{circle}"));
+    assert!(text.contains("O{circle,#ffcc00}"));
+    assert!(text.contains("6O{bold}"));
+    let imported = TggFile::from_text(&text).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_style_partial_round_trip() {
+    // puz only keeps circles, jpz loses bold borders
+    let circled = styled_fixture(false, false);
+    let (puz, warnings) = styled_fixture(true, true).to_puz().unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            Warning::PuzStyleIgnored { row: 2, column: 4 },
+            Warning::PuzStyleIgnored { row: 3, column: 0 },
+        ]
+    );
+    let (imported, warnings) = TggFile::from_puz(&puz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), circled.get_crossword());
+
+    let shaded = styled_fixture(true, false);
+    let (jpz, warnings) = styled_fixture(true, true).to_jpz().unwrap();
+    assert_eq!(
+        warnings,
+        vec![Warning::JpzStyleIgnored { row: 3, column: 0 }]
+    );
+    let (imported, warnings) = TggFile::from_jpz(&jpz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), shaded.get_crossword());
+}
+
+#[test]
+fn test_styles_need_format_version_4() {
+    let title_length = load_fixture().get_title().len();
+    let description_length = load_fixture().get_description().len();
+    let author_length = load_fixture().get_author().len();
+    let gamedata = 17 + title_length + description_length + author_length + 3 + 6;
+
+    let styled = styled_fixture(false, false).to_bytes();
+    assert_eq!(&styled[gamedata..gamedata + 2], &[0, 4]);
+
+    // The style section is a count, then the row, column, flags and color of each box
+    let end = styled.len() - 2;
+    assert_eq!(&styled[end - 17..end - 15], &5u16.to_le_bytes());
+    assert_eq!(&styled[end - 3..end], &[2, 4, 0b001]);
+
+    let shaded = styled_fixture(true, false).to_bytes();
+    let end = shaded.len() - 2;
+    assert_eq!(&shaded[end - 6..end], &[2, 4, 0b011, 0xff, 0xcc, 0x00]);
+}
+
+#[test]
+fn test_invalid_style_flags() {
+    let mut bytes = styled_fixture(false, false)
+        .get_crossword()
+        .unwrap()
+        .to_bytes();
+    let last = bytes.len() - 1;
+    bytes[last] = 0b1000;
+
+    assert!(CrosswordData::load(&bytes).is_err());
+}
+
+#[test]
+fn test_style_rendering() {
+    let crossword = styled_crossword();
+
+    let svg = crossword.render_svg(&SvgOptions::default());
+    assert_eq!(svg.matches("<circle").count(), 5);
+    assert!(svg.contains("fill=\"#ffcc00\""));
+    assert!(svg.contains("stroke-width=\"2.56\""));
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Solution,
+        clues: false,
+        ..TextRenderOptions::default()
+    });
+    assert!(text.contains("│(M)│(I)│(C)│(R)│(O)│"));
+
+    assert!(CellStyle::default().is_plain());
+    assert!(!crossword.crossword_data[2][0].style.is_plain());
+}
+
+#[test]
+fn test_styled_crossword_data_round_trip() {
+    let crossword = styled_crossword();
+    assert_eq!(
+        CrosswordData::load(&crossword.to_bytes()).unwrap(),
+        crossword
+    );
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::load_fixture;
+use tgg::render::RenderMode;
+use tgg::svg::{NumberPlacement, SvgOptions};
+
+#[test]
+fn test_render_svg_blank() {
+    let tgg_file = load_fixture();
+    let svg = tgg_file
+        .get_crossword()
+        .unwrap()
+        .render_svg(&SvgOptions::default());
+
+    assert!(svg.contains("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("width=\"192\" height=\"192\""));
+    assert_eq!(svg.matches("<rect").count(), 26);
+    assert_eq!(svg.matches("fill=\"#000000\"").count(), 6);
+    assert!(svg.contains(">7</text>"));
+    assert!(!svg.contains(">M</text>"));
+}
+
+#[test]
+fn test_render_svg_solution_with_clues() {
+    let tgg_file = load_fixture();
+    let options = SvgOptions {
+        mode: RenderMode::Solution,
+        cell_size: 40.0,
+        number_placement: NumberPlacement::TopRight,
+        solid_fill: "#333333".to_string(),
+        clues: true,
+        ..SvgOptions::default()
+    };
+
+    let svg = tgg_file.get_crossword().unwrap().render_svg(&options);
+
+    assert!(svg.contains(">M</text>"));
+    assert!(svg.contains("fill=\"#333333\""));
+    assert!(svg.contains("text-anchor=\"end\""));
+    assert!(svg.contains(">Across</text>"));
+    assert!(svg.contains(">To &quot;Alter&quot;, informally</text>"));
+}
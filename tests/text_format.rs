@@ -0,0 +1,110 @@
+mod common;
+
+use common::load_fixture;
+use tgg::{Error, TggFile};
+
+const PUZZLE: &str = "Title: Small
+Description: A tiny test puzzle
+Author: Talon Games
+Created: 1700000000
+
+Grid:
+1C 2A 3T
+4A .  O
+5B E  E
+
+Across:
+1. Feline
+4. Exist, plural
+5. Bee's cousin, briefly
+
+Down:
+1. Taxi
+2. Consumed
+3. Foot digit
+";
+
+#[test]
+fn test_parse_text() {
+    let tgg_file = TggFile::from_text(PUZZLE).unwrap();
+    let crossword = tgg_file.get_crossword().unwrap();
+
+    assert_eq!(tgg_file.get_title(), "Small");
+    assert_eq!(tgg_file.get_raw_creation_date(), 1700000000);
+    assert_eq!(crossword.width, 3);
+    assert_eq!(crossword.height, 3);
+    assert_eq!(crossword.horizontal_clues.len(), 3);
+    assert_eq!(crossword.vertical_clues[2].value, "Foot digit");
+}
+
+#[test]
+fn test_text_round_trip() {
+    let tgg_file = load_fixture();
+
+    let text = tgg_file.to_text();
+    let parsed = TggFile::from_text(&text).unwrap();
+
+    assert_eq!(parsed.to_text(), text);
+    assert_eq!(parsed.to_bytes(), tgg_file.to_bytes());
+}
+
+#[test]
+fn test_text_error_location() {
+    let text = PUZZLE.replace("4A .  O", "4A ?  O");
+
+    match TggFile::from_text(&text) {
+        Err(Error::TextSyntax { line, column, .. }) => {
+            assert_eq!(line, 8);
+            assert_eq!(column, 4);
+        }
+        other => panic!("expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_text_box_without_value() {
+    let location = |text: &str| match TggFile::from_text(text) {
+        Err(Error::TextSyntax { line, column, .. }) => (line, column),
+        other => panic!("expected a syntax error, got {:?}", other),
+    };
+
+    assert_eq!(location(&PUZZLE.replace("4A .  O", "4A 7  O")), (8, 5));
+    assert_eq!(
+        location(&PUZZLE.replace("4A .  O", "4A {circled} O")),
+        (8, 4)
+    );
+}
+
+#[test]
+fn test_text_empty_metadata() {
+    let location = |text: &str| match TggFile::from_text(text) {
+        Err(Error::TextSyntax { line, column, .. }) => (line, column),
+        other => panic!("expected a syntax error, got {:?}", other),
+    };
+
+    assert_eq!(location(&PUZZLE.replace("Title: Small", "Title:")), (1, 7));
+    assert_eq!(
+        location(&PUZZLE.replace("Author: Talon Games", "Author:   ")),
+        (3, 11)
+    );
+}
+
+#[test]
+fn test_puzzle_error_location() {
+    let location = |text: &str| match TggFile::from_text(text) {
+        Err(Error::TextSyntax { line, column, .. }) => (line, column),
+        other => panic!("expected a syntax error, got {:?}", other),
+    };
+
+    // Clues that don't match the grid point at the clue
+    assert_eq!(location(&PUZZLE.replace("5. Bee's", "6. Bee's")), (14, 1));
+    assert_eq!(
+        location(&PUZZLE.replace("3. Foot digit", "2. Foot digit")),
+        (19, 1)
+    );
+
+    // Numbers used twice point at the second box
+    assert_eq!(location(&PUZZLE.replace("5B E  E", "5B E  1E")), (9, 7));
+
+    assert_eq!(location(&PUZZLE.replace("4A .  O", "4A .")), (8, 1));
+}
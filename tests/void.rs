@@ -0,0 +1,148 @@
+mod common;
+
+use common::grid_values;
+use tgg::crossword::{CrosswordBoxValue, CrosswordClue, CrosswordData};
+use tgg::lint::{lint, LintConfig};
+use tgg::render::{Charset, RenderMode, TextRenderOptions};
+use tgg::svg::SvgOptions;
+use tgg::{TggFile, Warning};
+
+/// A plus shaped grid, with void corners:
+///
+/// ```text
+/// ~ 1A ~
+/// 2B C D
+/// ~ E  ~
+/// ```
+fn shaped_fixture() -> TggFile {
+    let crossword = CrosswordData::from_grid(
+        3,
+        3,
+        vec![CrosswordClue::new(2, "Across")],
+        vec![CrosswordClue::new(1, "Down")],
+        grid_values(&["~A~", "BCD", "~E~"]),
+    )
+    .unwrap();
+
+    TggFile::custom_crossword(
+        "Plus",
+        "A shaped grid",
+        "Talon Games",
+        3,
+        3,
+        crossword.horizontal_clues,
+        crossword.vertical_clues,
+        crossword.crossword_data,
+    )
+    .unwrap()
+}
+
+fn shaped_crossword() -> CrosswordData {
+    shaped_fixture().get_crossword().unwrap().clone()
+}
+
+#[test]
+fn test_void_bytes() {
+    let void = CrosswordBoxValue::Void;
+    assert_eq!(CrosswordBoxValue::from_byte(void.to_byte()).unwrap(), void);
+    assert!(!void.is_open());
+
+    let tgg_file = shaped_fixture();
+    let bytes = tgg_file.to_bytes();
+    let gamedata = 17 + "Plus".len() + "A shaped grid".len() + "Talon Games".len() + 3 + 6;
+    assert_eq!(&bytes[gamedata..gamedata + 2], &[0, 6]);
+
+    let loaded = TggFile::from_bytes(bytes).unwrap();
+    assert_eq!(loaded.get_crossword(), tgg_file.get_crossword());
+}
+
+#[test]
+fn test_void_ends_entries() {
+    let crossword = shaped_crossword();
+    crossword.validate_numbering().unwrap();
+    crossword.validate_clues().unwrap();
+
+    let answers: Vec<String> = crossword
+        .entries()
+        .into_iter()
+        .map(|entry| entry.answer)
+        .collect();
+    assert_eq!(answers, vec!["BCD", "ACE"]);
+}
+
+#[test]
+fn test_void_round_trip() {
+    let tgg_file = shaped_fixture();
+
+    let (imported, warnings) = TggFile::from_ipuz(&tgg_file.to_ipuz()).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let (jpz, warnings) = tgg_file.to_jpz().unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    let (imported, warnings) = TggFile::from_jpz(&jpz).unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    let text = tgg_file.to_text();
+    assert!(text.contains("~  1A ~"), "{}", text);
+    let imported = TggFile::from_text(&text).unwrap();
+    assert_eq!(imported.get_crossword(), tgg_file.get_crossword());
+
+    // .puz has no void boxes, they become blocks
+    let (puz, warnings) = tgg_file.to_puz().unwrap();
+    assert_eq!(warnings.len(), 4);
+    assert_eq!(
+        warnings[0],
+        Warning::PuzVoidCellIgnored { row: 0, column: 0 }
+    );
+    let (imported, _) = TggFile::from_puz(&puz).unwrap();
+    let crossword = imported.get_crossword().unwrap();
+    assert_eq!(
+        crossword.crossword_data[0][0].value,
+        CrosswordBoxValue::Solid
+    );
+    assert_eq!(crossword.entries().len(), 2);
+}
+
+#[test]
+fn test_void_rendering() {
+    let crossword = shaped_crossword();
+
+    // Only the five boxes are drawn, without a rectangle around the grid
+    let svg = crossword.render_svg(&SvgOptions::default());
+    assert_eq!(svg.matches("<rect").count(), 5);
+
+    let text = crossword.render_text(&TextRenderOptions {
+        mode: RenderMode::Solution,
+        charset: Charset::Ascii,
+        numbers: false,
+        color: false,
+        clues: false,
+    });
+    let expected = [
+        "    +---+",
+        "    | A |",
+        "+---+---+---+",
+        "| B | C | D |",
+        "+---+---+---+",
+        "    | E |",
+        "    +---+",
+    ];
+    assert_eq!(text.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_void_lint() {
+    let mut crossword = shaped_crossword();
+    let config = LintConfig {
+        rotational_symmetry: true,
+        connectivity: true,
+        ..LintConfig::default()
+    };
+    assert!(lint(&crossword, &config).is_empty());
+
+    // A block opposite a void box doesn't break symmetry
+    crossword.crossword_data[0][0].value = CrosswordBoxValue::Solid;
+    assert!(lint(&crossword, &config).is_empty());
+}
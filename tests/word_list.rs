@@ -0,0 +1,31 @@
+use tgg::word_list::{WordList, DEFAULT_SCORE};
+use tgg::Error;
+
+#[test]
+fn test_parse_word_list() {
+    let words = WordList::parse("# comment\ncat;40\nIce Cream;55\nCAT;10\nnaïve\n\ndog").unwrap();
+
+    assert_eq!(words.len(), 3);
+    assert_eq!(words.score("CAT"), Some(40));
+    assert_eq!(words.score("icecream"), Some(55));
+    assert_eq!(words.score("DOG"), Some(DEFAULT_SCORE));
+    assert!(!words.contains("NAIVE"));
+}
+
+#[test]
+fn test_word_list_matching() {
+    let words = WordList::parse("CAT\nCOT\nCAR\nDOG\nCATS").unwrap();
+
+    assert_eq!(words.matching("C?T"), vec!["CAT", "COT"]);
+    assert_eq!(words.matching("???"), vec!["CAT", "COT", "CAR", "DOG"]);
+    assert!(words.matching("X??").is_empty());
+    assert!(words.matching("?????").is_empty());
+}
+
+#[test]
+fn test_word_list_invalid_score() {
+    assert!(matches!(
+        WordList::parse("CAT;40\nDOG;lots"),
+        Err(Error::InvalidWordListScore { line: 2 })
+    ));
+}
@@ -0,0 +1,182 @@
+use tgg::word_search::{WordDirection, WordSearchData};
+use tgg::word_search_generator::WordSearchOptions;
+use tgg::{Game, TggFile, Warning};
+
+const WORDS: [&str; 8] = [
+    "Apple", "Banana", "Cherry", "Grape", "Lemon", "Mango", "Orange", "Pear",
+];
+
+/// Every place a word is spelled, as its start and direction.
+fn occurrences(word_search: &WordSearchData, word: &str) -> Vec<(i32, i32, WordDirection)> {
+    let letters: Vec<char> = word.chars().collect();
+    let mut found = Vec::new();
+
+    for row in 0..word_search.height as i32 {
+        for column in 0..word_search.width as i32 {
+            for direction in WordDirection::ALL {
+                let (row_step, column_step) = direction.steps();
+                let spelled = letters.iter().enumerate().all(|(offset, letter)| {
+                    let r = row + row_step * offset as i32;
+                    let c = column + column_step * offset as i32;
+                    r >= 0
+                        && c >= 0
+                        && word_search
+                            .grid
+                            .get(r as usize)
+                            .and_then(|cells| cells.get(c as usize))
+                            == Some(letter)
+                });
+                if spelled {
+                    found.push((row, column, direction));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+#[test]
+fn test_generate_word_search() {
+    let (tgg_file, warnings) = TggFile::generate_word_search(
+        "Fruit",
+        "Find the fruit",
+        "Talon Games",
+        &WORDS,
+        &WordSearchOptions::default(),
+    )
+    .unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(*tgg_file.get_game(), Game::WordSearch);
+
+    let word_search = tgg_file.get_word_search().unwrap();
+    assert_eq!((word_search.width, word_search.height), (12, 12));
+    assert_eq!(word_search.words.len(), 8);
+    for word in &word_search.words {
+        assert_eq!(occurrences(word_search, word).len(), 1, "{}", word);
+    }
+
+    let loaded = TggFile::from_bytes(tgg_file.to_bytes()).unwrap();
+    assert_eq!(loaded.get_word_search(), Some(word_search));
+}
+
+#[test]
+fn test_generate_word_search_directions_and_seed() {
+    let options = WordSearchOptions {
+        width: 10,
+        height: 10,
+        vertical: false,
+        diagonal: false,
+        reversed: true,
+        seed: 3,
+        ..WordSearchOptions::default()
+    };
+
+    let (tgg_file, _) =
+        TggFile::generate_word_search("Fruit", "", "Talon Games", &WORDS, &options).unwrap();
+    let word_search = tgg_file.get_word_search().unwrap();
+
+    for word in &word_search.words {
+        let (_, _, direction) = occurrences(word_search, word)[0];
+        assert!(matches!(
+            direction,
+            WordDirection::Right | WordDirection::Left
+        ));
+    }
+
+    let (again, _) =
+        TggFile::generate_word_search("Fruit", "", "Talon Games", &WORDS, &options).unwrap();
+    assert_eq!(again.get_word_search(), Some(word_search));
+}
+
+#[test]
+fn test_generate_word_search_unplaced_words() {
+    let options = WordSearchOptions {
+        width: 5,
+        height: 5,
+        ..WordSearchOptions::default()
+    };
+
+    let (tgg_file, warnings) = TggFile::generate_word_search(
+        "Fruit",
+        "",
+        "Talon Games",
+        &["Apple", "Watermelon", "Kiwi", "kiwi"],
+        &options,
+    )
+    .unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![
+            Warning::WordNotPlaced {
+                word: "Watermelon".to_string()
+            },
+            Warning::WordNotPlaced {
+                word: "kiwi".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        tgg_file.get_word_search().unwrap().words,
+        vec!["APPLE", "KIWI"]
+    );
+}
+
+#[test]
+fn test_generate_word_search_words_inside_others() {
+    let (tgg_file, warnings) = TggFile::generate_word_search(
+        "Pets",
+        "",
+        "Talon Games",
+        &["Cats", "Cat", "Tac"],
+        &WordSearchOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![
+            Warning::WordInsideAnother {
+                word: "Cat".to_string(),
+                other: "CATS".to_string()
+            },
+            Warning::WordInsideAnother {
+                word: "Tac".to_string(),
+                other: "CATS".to_string()
+            },
+        ]
+    );
+    assert_eq!(tgg_file.get_word_search().unwrap().words, vec!["CATS"]);
+}
+
+#[test]
+fn test_generate_word_search_leftover_duplicates() {
+    // The grid is too small to keep placed words from spelling each other
+    let options = WordSearchOptions {
+        width: 3,
+        height: 3,
+        seed: 2,
+        ..WordSearchOptions::default()
+    };
+
+    let (tgg_file, warnings) = TggFile::generate_word_search(
+        "Letters",
+        "",
+        "Talon Games",
+        &["ABC", "CDE", "BCD"],
+        &options,
+    )
+    .unwrap();
+
+    let word_search = tgg_file.get_word_search().unwrap();
+    let mut repeated = 0;
+    for word in &word_search.words {
+        if occurrences(word_search, word).len() > 1 {
+            repeated += 1;
+            assert!(warnings.contains(&Warning::WordFoundTwice { word: word.clone() }));
+        }
+    }
+    assert!(repeated > 0);
+}
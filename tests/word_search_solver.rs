@@ -0,0 +1,89 @@
+use tgg::word_list::WordList;
+use tgg::word_search::{WordDirection, WordSearchData};
+use tgg::word_search_generator::WordSearchOptions;
+use tgg::TggFile;
+
+fn word_search(words: &[&str], rows: &[&str]) -> WordSearchData {
+    WordSearchData::new(
+        rows[0].len() as u8,
+        rows.len() as u8,
+        words.iter().map(|word| word.to_string()).collect(),
+        rows.iter().map(|row| row.chars().collect()).collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_solve_word_search() {
+    let word_search = word_search(&["CAT", "DOG", "OWL"], &["CATX", "XOXD", "XXWO", "LWOG"]);
+
+    let solution = word_search.solve();
+    assert!(solution.missing.is_empty());
+    assert!(solution.ambiguous.is_empty());
+    assert!(!word_search.is_ambiguous());
+
+    let cat = word_search.locate("CAT").unwrap();
+    assert_eq!((cat.row, cat.column), (0, 0));
+    assert_eq!(cat.direction, WordDirection::Right);
+    assert_eq!(cat.length, 3);
+
+    let dog = word_search.locate("DOG").unwrap();
+    assert_eq!((dog.row, dog.column), (1, 3));
+    assert_eq!(dog.direction, WordDirection::Down);
+    assert_eq!(dog.cells(), vec![(1, 3), (2, 3), (3, 3)]);
+
+    let owl = word_search.locate("OWL").unwrap();
+    assert_eq!((owl.row, owl.column), (3, 2));
+    assert_eq!(owl.direction, WordDirection::Left);
+}
+
+#[test]
+fn test_missing_and_ambiguous_words() {
+    let word_search = word_search(&["CAT", "EEL"], &["CATX", "XAXX", "XXTX", "TACX"]);
+
+    let solution = word_search.solve();
+    assert_eq!(solution.missing, vec!["EEL".to_string()]);
+    assert_eq!(solution.ambiguous, vec!["CAT".to_string()]);
+    assert_eq!(solution.found.len(), 3);
+    assert!(word_search.locate("CAT").is_none());
+    assert!(word_search.is_ambiguous());
+}
+
+#[test]
+fn test_palindromes_are_found_once() {
+    let word_search = word_search(&["RADAR"], &["RADAR", "XXXXX"]);
+
+    let solution = word_search.solve();
+    assert_eq!(solution.found.len(), 1);
+    assert!(solution.ambiguous.is_empty());
+}
+
+#[test]
+fn test_unlisted_dictionary_words() {
+    let word_search = word_search(&["CAT"], &["CATS", "XXXX", "XRAT"]);
+    let dictionary = WordList::parse("CAT\nCATS\nRAT\nAT\nZEBRA").unwrap();
+
+    let solution = word_search.solve_with_dictionary(&dictionary, 3);
+    assert_eq!(solution.found.len(), 1);
+
+    let mut unlisted: Vec<&str> = solution.unlisted.iter().map(|l| l.word.as_str()).collect();
+    unlisted.sort();
+    assert_eq!(unlisted, vec!["CATS", "RAT"]);
+}
+
+#[test]
+fn test_solve_generated_word_search() {
+    let words = ["Apple", "Banana", "Cherry", "Grape", "Lemon", "Mango"];
+    let options = WordSearchOptions {
+        reversed: true,
+        seed: 7,
+        ..WordSearchOptions::default()
+    };
+    let (tgg_file, _) = TggFile::generate_word_search("Fruit", "", "Me", &words, &options).unwrap();
+    let word_search = tgg_file.get_word_search().unwrap();
+
+    let solution = word_search.solve();
+    assert!(solution.missing.is_empty());
+    assert!(solution.ambiguous.is_empty());
+    assert_eq!(solution.found.len(), word_search.words.len());
+}